@@ -1,12 +1,25 @@
+use crate::board::Board;
+use crate::export;
 use crate::field::Field;
+use crate::field::Generator;
+use crate::field::MinesAmount;
+use crate::field::Opening;
 use crate::game::Game;
 use crate::net::client::Client;
 use crate::net::server::Server;
 use crate::net::NetHandler;
+use crate::profile;
+use crate::profile::Profile;
+use crate::sapper::Guessing;
 use crate::sapper::Sapper;
 use crate::sapper::SapperBehavior;
+use crate::sapper::Teamwork;
+use crate::scoring::Scoring;
+use crate::skill::Skill;
+use crate::topology::HexTopology;
 use crate::ui::button::Button;
 use crate::ui::input_number::InputNumber;
+use crate::ui::input_select::InputSelect;
 use crate::ui::input_text::InputText;
 use crate::ui::menu::Menu;
 use crate::ui::page::Page;
@@ -15,7 +28,10 @@ use crate::ui::text::Text;
 use crate::ui::Event;
 use crate::utils;
 use futures::executor::block_on;
+use std::path::Path;
 use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 use termwiz::caps::Capabilities;
 use termwiz::color::ColorAttribute;
 use termwiz::input::InputEvent;
@@ -30,30 +46,110 @@ use termwiz::terminal::Terminal;
 
 const MAIN: &str = "Main menu";
 const CONTINUE: &str = "Continue";
+const EXPORT: &str = "Export";
 const NEW_GAME: &str = "New game";
 const START: &str = "Start";
 const RESET: &str = "Reset";
+const SAVE_PROFILE: &str = "Save profile";
+const SCORING: &str = "Scoring";
+const BOTS_SKILLS: &str = "Bots skills";
 const JOIN_GAME: &str = "Join game";
 const JOIN: &str = "Join";
 const HELP: &str = "Help";
 const BACK: &str = "Back";
 const QUIT: &str = "Quit";
-const FIELD_SIZE: &str = "Field size   ";
+const PROFILE: &str = "Profile      ";
+const PROFILE_NAME: &str = "Profile name ";
+const BOARD: &str = "Board        ";
+const BOARD_FINITE: &str = "Finite";
+const BOARD_INFINITE: &str = "Infinite";
+const FIELD_WIDTH: &str = "Field width  ";
+const FIELD_HEIGHT: &str = "Field height ";
+const GRID: &str = "Grid         ";
+const GRID_SQUARE: &str = "Square";
+const GRID_HEX: &str = "Hex";
+const EDGES: &str = "Edges        ";
+const EDGES_BOUNDED: &str = "Bounded";
+const EDGES_WRAPPED: &str = "Wrapped";
+const MINES_MODE: &str = "Mines mode   ";
+const MINES_MODE_DENSITY: &str = "Density";
+const MINES_MODE_COUNT: &str = "Count";
 const MINES_DENSITY: &str = "Mines density";
+const MINES_COUNT: &str = "Mines count  ";
+const GENERATOR: &str = "Generator    ";
+const GENERATOR_RANDOM: &str = "Random";
+const GENERATOR_NO_GUESS: &str = "No guess";
+const OPENING: &str = "Opening      ";
+const OPENING_UNPROTECTED: &str = "Unprotected";
+const OPENING_SAFE_CELL: &str = "Safe cell";
+const OPENING_SAFE_AREA: &str = "Safe area";
+const OPENING_ZERO: &str = "Zero";
+const SEED: &str = "Seed         ";
+const BOARD_FILE: &str = "Board file   ";
 const BOTS: &str = "Bots         ";
 const BOTS_REACTION: &str = "Bots reaction";
+const BOTS_GUESSING: &str = "Bots guessing";
+const BOTS_GUESSING_NEVER: &str = "Never";
+const BOTS_GUESSING_CAUTIOUS: &str = "Cautious";
+const BOTS_GUESSING_ALWAYS: &str = "Always";
+const BOT_SKILLS: [&str; 8] = [
+    "Bot 1        ",
+    "Bot 2        ",
+    "Bot 3        ",
+    "Bot 4        ",
+    "Bot 5        ",
+    "Bot 6        ",
+    "Bot 7        ",
+    "Bot 8 & rest ",
+];
+const BOTS_RIVALS: &str = "Bots rivals  ";
+const BOTS_RIVALS_IGNORED: &str = "Ignored";
+const BOTS_RIVALS_AVOIDED: &str = "Avoided";
+const BOTS_TEAMWORK: &str = "Bots teamwork";
+const BOTS_TEAMWORK_COMPETITIVE: &str = "Competitive";
+const BOTS_TEAMWORK_COOPERATIVE: &str = "Cooperative";
+const LIVES: &str = "Lives        ";
+const TIME_LIMIT: &str = "Time limit   ";
+const SCORING_DISCOVERY: &str = "Discovery    ";
+const SCORING_CELL: &str = "Revealed cell";
+const SCORING_FLAG_BONUS: &str = "Flag bonus   ";
+const SCORING_FLAG_PENALTY: &str = "Flag penalty ";
+const SCORING_EXPLOSION: &str = "Explosion    ";
+const SCORING_CLEAR_BONUS: &str = "Clear bonus  ";
+const UNSURE_MARKS: &str = "Unsure marks ";
+const UNSURE_MARKS_BLOCKING: &str = "Blocking";
+const UNSURE_MARKS_PASSABLE: &str = "Passable";
 const SERVER_IP: &str = "Server IP    ";
 const SERVER_PORT: &str = "Server port  ";
 const ERROR: &str = "Error";
 const DISCONNECTED: &str = "Disconnected";
 
-const DEFAULT_FILED_SIZE: u8 = 8;
+const DEFAULT_PROFILE: &str = profile::CUSTOM;
+const DEFAULT_BOARD: &str = BOARD_FINITE;
+const DEFAULT_FIELD_WIDTH: u16 = 8;
+const DEFAULT_FIELD_HEIGHT: u16 = 8;
+const DEFAULT_GRID: &str = GRID_SQUARE;
+const DEFAULT_EDGES: &str = EDGES_BOUNDED;
+const DEFAULT_MINES_MODE: &str = MINES_MODE_DENSITY;
 const DEFAULT_MINES_DENSITY: f64 = 0.2;
+const DEFAULT_MINES_COUNT: u32 = 10;
+const DEFAULT_GENERATOR: &str = GENERATOR_RANDOM;
+const DEFAULT_OPENING: &str = OPENING_SAFE_AREA;
 const DEFAULT_BOTS: u8 = 0;
 const DEFAULT_BOTS_REACTION: f64 = 1.0;
+const DEFAULT_BOTS_GUESSING: &str = BOTS_GUESSING_ALWAYS;
+const DEFAULT_BOTS_RIVALS: &str = BOTS_RIVALS_IGNORED;
+const DEFAULT_BOTS_TEAMWORK: &str = BOTS_TEAMWORK_COMPETITIVE;
+const DEFAULT_LIVES: u8 = 1;
+const DEFAULT_TIME_LIMIT: u32 = 0;
+const DEFAULT_SCORING: Scoring = Scoring::new();
+const DEFAULT_UNSURE_MARKS: &str = UNSURE_MARKS_BLOCKING;
 const DEFAULT_SERVER_IP: &str = "127.0.0.1";
 const DEFAULT_SERVER_PORT: &str = "6000";
 
+const PROFILES_FILE: &str = concat!(env!("CARGO_PKG_NAME"), "-profiles.json");
+const INFINITE_FIELD_SIZE: u16 = u16::MAX - 1; // Even, so hex rows keep alternating across the edges
+
 #[derive(PartialEq)]
 pub enum ScreenUpdate {
     None,
@@ -63,6 +159,8 @@ pub enum ScreenUpdate {
 
 pub struct Application {
     menu: Menu,
+    profiles: Vec<Profile>,
+    profile_applied: String,
     server: Option<Server>,
     client: Option<Client>,
     is_running: bool,
@@ -72,8 +170,13 @@ pub struct Application {
 
 impl Application {
     pub fn new() -> Self {
+        let mut profiles = profile::get_builtin();
+        profiles.append(&mut profile::load(Path::new(PROFILES_FILE)));
+
         return Self {
-            menu: Self::init_menu(),
+            menu: Self::init_menu(&profiles),
+            profiles,
+            profile_applied: DEFAULT_PROFILE.to_owned(),
             server: None,
             client: None,
             is_running: false,
@@ -82,12 +185,13 @@ impl Application {
         };
     }
 
-    fn init_menu() -> Menu {
+    fn init_menu(profiles: &[Profile]) -> Menu {
         let mut menu = Menu::new();
 
         {
             let mut page_main = Page::new(MAIN);
             page_main.elements.push(Box::new(Button::new(CONTINUE, false)));
+            page_main.elements.push(Box::new(Button::new(EXPORT, false)));
             page_main.elements.push(Box::new(Button::new(NEW_GAME, true)));
             page_main.elements.push(Box::new(Button::new(JOIN_GAME, true)));
             page_main.elements.push(Box::new(Button::new(HELP, true)));
@@ -96,69 +200,17 @@ impl Application {
             menu.add(page_main);
         }
 
-        {
-            let mut new_game = Page::new(NEW_GAME);
-
-            new_game.elements.push(Box::new(InputNumber::new(
-                FIELD_SIZE,
-                f64::from(DEFAULT_FILED_SIZE),
-                1.0,
-                32.0,
-                1.0,
-                None,
-            )));
-
-            new_game.elements.push(Box::new(InputNumber::new(
-                MINES_DENSITY,
-                DEFAULT_MINES_DENSITY,
-                0.0,
-                1.0,
-                0.01,
-                Some("The probability that a cell will have a mine. 0 - no mines, 1 - every cell will be mined."),
-            )));
-
-            new_game.elements.push(Box::new(InputNumber::new(
-                BOTS,
-                f64::from(DEFAULT_BOTS),
-                0.0,
-                254.0,
-                1.0,
-                Some("The number of rival bots who will try to sweep mines as you too."),
-            )));
-
-            new_game.elements.push(Box::new(InputNumber::new(
-                BOTS_REACTION,
-                DEFAULT_BOTS_REACTION,
-                0.1,
-                5.0,
-                0.1,
-                Some("The time in seconds for a bot to make a move."),
-            )));
-
-            let mut server_ip = InputText::new(
-                SERVER_IP,
-                Some("IPv4 or IPv6 address."), // TODO: Verify
-            );
-            server_ip.value = DEFAULT_SERVER_IP.to_owned();
-
-            let mut server_port = InputText::new(SERVER_PORT, None);
-            server_port.value = DEFAULT_SERVER_PORT.to_owned();
-
-            new_game.elements.push(Box::new(server_ip));
-            new_game.elements.push(Box::new(server_port));
-            new_game.elements.push(Box::new(Spacer::new()));
-            new_game.elements.push(Box::new(Button::new(START, true)));
-            new_game.elements.push(Box::new(Button::new(RESET, true)));
-            new_game.elements.push(Box::new(Button::new(BACK, true)));
-            new_game.reset_cursor();
-            menu.add(new_game);
-        }
+        menu.add(Self::init_page_new_game(profiles));
+        menu.add(Self::init_page_scoring());
+        menu.add(Self::init_page_bots_skills());
 
         {
             let help_text = "\
                 - Use the arrow keys to move around the field\r\n\
-                - Press `M` to mark a cell\r\n\
+                - Use `Home`, `PageUp`, `End` and `PageDown` to move diagonally on a hex field\r\n\
+                - Press `M` to mark a cell, press it again to turn the mark into a question\r\n\
                 - Press `Space` to discover a cell\r\n\
+                - Press `C` on a number to discover its unmarked neighbors once all its mines are marked\r\n\
                 - Press `Escape` to switch between the game and menu\
                 ".to_owned();
 
@@ -172,6 +224,7 @@ impl Application {
 
         {
             let mut join = Page::new(JOIN_GAME);
+            join.elements.push(Box::new(Self::init_input_unsure_marks()));
 
             let mut server_ip = InputText::new(
                 SERVER_IP,
@@ -194,6 +247,309 @@ impl Application {
         return menu;
     }
 
+    #[allow(clippy::too_many_lines)] // TODO: Resolve later
+    fn init_page_new_game(profiles: &[Profile]) -> Page {
+        let mut new_game = Page::new(NEW_GAME);
+
+        let mut profile_names = vec![profile::CUSTOM];
+        profile_names.extend(profiles.iter().map(|p| p.name.as_str()));
+
+        let mut profile = InputSelect::new(
+            PROFILE,
+            &profile_names,
+            Some("Sets the field size, mines and bots at once. Changing any of them turns the profile into a custom one."),
+        );
+        profile.select(DEFAULT_PROFILE);
+        new_game.elements.push(Box::new(profile));
+
+        new_game.elements.push(Box::new(InputText::new(
+            PROFILE_NAME,
+            Some("A name to save the current settings under with the `Save profile` button."),
+        )));
+
+        let mut board = InputSelect::new(
+            BOARD,
+            &[BOARD_FINITE, BOARD_INFINITE],
+            Some("An infinite board is a huge wrapped field which is generated chunk by chunk while sappers explore it. The field size and generator settings are ignored."),
+        );
+        board.select(DEFAULT_BOARD);
+        new_game.elements.push(Box::new(board));
+
+        new_game.elements.push(Box::new(InputNumber::new(
+            FIELD_WIDTH,
+            f64::from(DEFAULT_FIELD_WIDTH),
+            1.0,
+            1024.0,
+            1.0,
+            None,
+        )));
+
+        new_game.elements.push(Box::new(InputNumber::new(
+            FIELD_HEIGHT,
+            f64::from(DEFAULT_FIELD_HEIGHT),
+            1.0,
+            1024.0,
+            1.0,
+            None,
+        )));
+
+        let mut grid = InputSelect::new(
+            GRID,
+            &[GRID_SQUARE, GRID_HEX],
+            Some("Square cells have 8 neighbors while hexagonal ones have 6."),
+        );
+        grid.select(DEFAULT_GRID);
+        new_game.elements.push(Box::new(grid));
+
+        let mut edges = InputSelect::new(
+            EDGES,
+            &[EDGES_BOUNDED, EDGES_WRAPPED],
+            Some("Wrapped edges join the opposite sides of the field, so neighbors and movement continue across them."),
+        );
+        edges.select(DEFAULT_EDGES);
+        new_game.elements.push(Box::new(edges));
+
+        let mut mines_mode = InputSelect::new(
+            MINES_MODE,
+            &[MINES_MODE_DENSITY, MINES_MODE_COUNT],
+            Some("Whether to roll every cell against the density or to place an exact number of mines."),
+        );
+        mines_mode.select(DEFAULT_MINES_MODE);
+        new_game.elements.push(Box::new(mines_mode));
+
+        new_game.elements.push(Box::new(InputNumber::new(
+            MINES_DENSITY,
+            DEFAULT_MINES_DENSITY,
+            0.0,
+            1.0,
+            0.01,
+            Some("The probability that a cell will have a mine. 0 - no mines, 1 - every cell will be mined."),
+        )));
+
+        new_game.elements.push(Box::new(InputNumber::new(
+            MINES_COUNT,
+            f64::from(DEFAULT_MINES_COUNT),
+            0.0,
+            1_048_576.0,
+            1.0,
+            Some("The exact number of mines to place in the count mode."),
+        )));
+
+        let mut generator = InputSelect::new(
+            GENERATOR,
+            &[GENERATOR_RANDOM, GENERATOR_NO_GUESS],
+            Some("The no guess generator makes sure the field can be cleared by logic alone from the first discovered cell."),
+        );
+        generator.select(DEFAULT_GENERATOR);
+        new_game.elements.push(Box::new(generator));
+
+        let mut opening = InputSelect::new(
+            OPENING,
+            &[
+                OPENING_UNPROTECTED,
+                OPENING_SAFE_CELL,
+                OPENING_SAFE_AREA,
+                OPENING_ZERO,
+            ],
+            Some("What is kept free of mines around the first discovered cell. Every discovery made by other sappers at the same moment counts as the first one too."),
+        );
+        opening.select(DEFAULT_OPENING);
+        new_game.elements.push(Box::new(opening));
+
+        new_game.elements.push(Box::new(InputText::new(
+            SEED,
+            Some("A number which defines mines and spawns. Leave it empty to pick a random one."),
+        )));
+
+        new_game.elements.push(Box::new(InputText::new(
+            BOARD_FILE,
+            Some("A path to a board with predefined mines, either a text file where `*` is a mine, `.` is a safe cell and `o` is a discovered one, or an `.mbf` file. The field size and mines settings are ignored then. Leave it empty to generate mines."),
+        )));
+
+        new_game.elements.push(Box::new(InputNumber::new(
+            BOTS,
+            f64::from(DEFAULT_BOTS),
+            0.0,
+            254.0,
+            1.0,
+            Some("The number of rival bots who will try to sweep mines as you too."),
+        )));
+
+        new_game.elements.push(Box::new(InputNumber::new(
+            BOTS_REACTION,
+            DEFAULT_BOTS_REACTION,
+            0.1,
+            5.0,
+            0.1,
+            Some("The time in seconds for a bot to make a move."),
+        )));
+
+        let mut bots_guessing = InputSelect::new(
+            BOTS_GUESSING,
+            &[
+                BOTS_GUESSING_NEVER,
+                BOTS_GUESSING_CAUTIOUS,
+                BOTS_GUESSING_ALWAYS,
+            ],
+            Some("What bots do once nothing is certain. They pick the cell which is the least likely to be mined, a cautious bot only does when the risk is low."),
+        );
+        bots_guessing.select(DEFAULT_BOTS_GUESSING);
+        new_game.elements.push(Box::new(bots_guessing));
+
+        let mut bots_rivals = InputSelect::new(
+            BOTS_RIVALS,
+            &[BOTS_RIVALS_IGNORED, BOTS_RIVALS_AVOIDED],
+            Some("Whether bots leave the cells other sappers are heading to and keep out of their way, or race them for the same cells."),
+        );
+        bots_rivals.select(DEFAULT_BOTS_RIVALS);
+        new_game.elements.push(Box::new(bots_rivals));
+
+        let mut bots_teamwork = InputSelect::new(
+            BOTS_TEAMWORK,
+            &[BOTS_TEAMWORK_COMPETITIVE, BOTS_TEAMWORK_COOPERATIVE],
+            Some("Cooperative bots share a board of claimed targets, so each of them works on its own part of the field and only one guesses at a time. Competitive bots scan the whole field on their own."),
+        );
+        bots_teamwork.select(DEFAULT_BOTS_TEAMWORK);
+        new_game.elements.push(Box::new(bots_teamwork));
+
+        new_game.elements.push(Box::new(InputNumber::new(
+            LIVES,
+            f64::from(DEFAULT_LIVES),
+            1.0,
+            9.0,
+            1.0,
+            Some("How many mines every sapper can hit before it dies. Each extra hit costs some score but the mine stays exploded."),
+        )));
+
+        new_game.elements.push(Box::new(InputNumber::new(
+            TIME_LIMIT,
+            f64::from(DEFAULT_TIME_LIMIT),
+            0.0,
+            600.0,
+            1.0,
+            Some("The round length in minutes, counted from the first discovery. The survivor with the best score wins once it's over. Zero means no limit."),
+        )));
+
+        new_game
+            .elements
+            .push(Box::new(Self::init_input_unsure_marks()));
+
+        let mut server_ip = InputText::new(
+            SERVER_IP,
+            Some("IPv4 or IPv6 address."), // TODO: Verify
+        );
+        server_ip.value = DEFAULT_SERVER_IP.to_owned();
+
+        let mut server_port = InputText::new(SERVER_PORT, None);
+        server_port.value = DEFAULT_SERVER_PORT.to_owned();
+
+        new_game.elements.push(Box::new(server_ip));
+        new_game.elements.push(Box::new(server_port));
+        new_game.elements.push(Box::new(Spacer::new()));
+        new_game.elements.push(Box::new(Button::new(START, true)));
+        new_game.elements.push(Box::new(Button::new(SCORING, true)));
+        new_game.elements.push(Box::new(Button::new(BOTS_SKILLS, true)));
+        new_game.elements.push(Box::new(Button::new(SAVE_PROFILE, true)));
+        new_game.elements.push(Box::new(Button::new(RESET, true)));
+        new_game.elements.push(Box::new(Button::new(BACK, true)));
+        new_game.reset_cursor();
+        return new_game;
+    }
+
+    fn init_page_scoring() -> Page {
+        let mut scoring = Page::new(SCORING);
+
+        scoring.elements.push(Box::new(InputNumber::new(
+            SCORING_DISCOVERY,
+            f64::from(DEFAULT_SCORING.discovery),
+            0.0,
+            1000.0,
+            1.0,
+            Some("Points for every discovery."),
+        )));
+
+        scoring.elements.push(Box::new(InputNumber::new(
+            SCORING_CELL,
+            f64::from(DEFAULT_SCORING.cell),
+            0.0,
+            1000.0,
+            1.0,
+            Some("Points for every cell a discovery reveals, including the ones opened around empty cells."),
+        )));
+
+        scoring.elements.push(Box::new(InputNumber::new(
+            SCORING_FLAG_BONUS,
+            f64::from(DEFAULT_SCORING.flag_bonus),
+            0.0,
+            1000.0,
+            1.0,
+            Some("Points for every flag on a mine when the game is over."),
+        )));
+
+        scoring.elements.push(Box::new(InputNumber::new(
+            SCORING_FLAG_PENALTY,
+            f64::from(DEFAULT_SCORING.flag_penalty),
+            0.0,
+            1000.0,
+            1.0,
+            Some("Points taken for every flag on a safe cell when the game is over."),
+        )));
+
+        scoring.elements.push(Box::new(InputNumber::new(
+            SCORING_EXPLOSION,
+            f64::from(DEFAULT_SCORING.explosion_penalty),
+            0.0,
+            1000.0,
+            1.0,
+            Some("Points taken for every mine hit a sapper survives."),
+        )));
+
+        scoring.elements.push(Box::new(InputNumber::new(
+            SCORING_CLEAR_BONUS,
+            f64::from(DEFAULT_SCORING.clear_bonus),
+            0.0,
+            1000.0,
+            1.0,
+            Some("Points for the sapper who discovers the last safe cell."),
+        )));
+
+        scoring.elements.push(Box::new(Spacer::new()));
+        scoring.elements.push(Box::new(Button::new(BACK, true)));
+        scoring.reset_cursor();
+        return scoring;
+    }
+
+    fn init_page_bots_skills() -> Page {
+        let mut bots_skills = Page::new(BOTS_SKILLS);
+        let options = Skill::ALL.map(Skill::get_name);
+
+        for label in BOT_SKILLS {
+            let mut skill = InputSelect::new(
+                label,
+                &options,
+                Some("Weaker bots see only the obvious, slip now and then, wander around and guess reluctantly. The last skill goes for the rest of the bots too."),
+            );
+
+            skill.select(Skill::DEFAULT.get_name());
+            bots_skills.elements.push(Box::new(skill));
+        }
+
+        bots_skills.elements.push(Box::new(Spacer::new()));
+        bots_skills.elements.push(Box::new(Button::new(BACK, true)));
+        bots_skills.reset_cursor();
+        return bots_skills;
+    }
+
+    fn init_input_unsure_marks() -> InputSelect {
+        let mut unsure_marks = InputSelect::new(
+            UNSURE_MARKS,
+            &[UNSURE_MARKS_BLOCKING, UNSURE_MARKS_PASSABLE],
+            Some("Whether a cell marked with a question can be discovered. Press `M` to cycle between a flag, a question and no mark."),
+        );
+        unsure_marks.select(DEFAULT_UNSURE_MARKS);
+        return unsure_marks;
+    }
+
     #[allow(clippy::too_many_lines)] // TODO: Resolve later
     pub fn run(&mut self) {
         let mut terminal = BufferedTerminal::new(new_terminal(Capabilities::new_from_env().unwrap()).unwrap()).unwrap();
@@ -212,7 +568,8 @@ impl Application {
                 if self.is_menu {
                     terminal.draw_from_screen(&self.menu.render(), 0, 0);
                 } else if let Some(client) = self.client.as_ref() {
-                    terminal.draw_from_screen(&client.game.render(), 0, 0);
+                    let (width, height) = terminal.dimensions();
+                    terminal.draw_from_screen(&client.game.render(width, height), 0, 0);
                 }
 
                 terminal.add_change(Change::CursorPosition {
@@ -258,15 +615,29 @@ impl Application {
                                         Event::ButtonPressed(CONTINUE) => {
                                             self.toggle_menu();
                                         }
+                                        Event::ButtonPressed(EXPORT) => {
+                                            self.export_game();
+                                        }
                                         Event::ButtonPressed(NEW_GAME) => {
                                             self.menu.open(NEW_GAME);
                                         }
                                         Event::ButtonPressed(START) => {
                                             self.start_new_game_safely(true);
                                         }
+                                        Event::ButtonPressed(SCORING) => {
+                                            self.menu.open(SCORING);
+                                        }
+                                        Event::ButtonPressed(BOTS_SKILLS) => {
+                                            self.menu.open(BOTS_SKILLS);
+                                        }
                                         Event::ButtonPressed(JOIN) => {
                                             self.start_new_game_safely(false);
                                         }
+                                        Event::ButtonPressed(SAVE_PROFILE) => {
+                                            if let Err(error) = self.save_profile() {
+                                                self.menu.show_message(error, ERROR, BACK);
+                                            }
+                                        }
                                         Event::ButtonPressed(RESET) => {
                                             self.reset_settings();
                                         }
@@ -283,6 +654,7 @@ impl Application {
                                             self.stop();
                                         }
                                         Event::PageChanged => {
+                                            self.sync_profile();
                                             self.set_screen_update(ScreenUpdate::Partial);
                                         }
                                         Event::MenuChanged => {
@@ -332,24 +704,81 @@ impl Application {
         terminal.flush().unwrap();
     }
 
+    #[allow(clippy::too_many_lines)] // TODO: Resolve later
     fn start_new_game(&mut self, is_host: bool) -> Result<(), String> {
         self.stop_game();
 
-        let mut field_size = DEFAULT_FILED_SIZE;
+        let mut board = DEFAULT_BOARD.to_owned();
+        let mut field_width = DEFAULT_FIELD_WIDTH;
+        let mut field_height = DEFAULT_FIELD_HEIGHT;
+        let mut grid = DEFAULT_GRID.to_owned();
+        let mut edges = DEFAULT_EDGES.to_owned();
+        let mut mines_mode = DEFAULT_MINES_MODE.to_owned();
         let mut mines_density = DEFAULT_MINES_DENSITY;
+        let mut mines_count = DEFAULT_MINES_COUNT;
+        let mut generator = DEFAULT_GENERATOR.to_owned();
+        let mut opening = DEFAULT_OPENING.to_owned();
+        let mut seed = String::new();
+        let mut board_file = String::new();
         let mut bots = DEFAULT_BOTS;
         let mut bots_reaction = DEFAULT_BOTS_REACTION;
+        let mut bots_guessing = DEFAULT_BOTS_GUESSING.to_owned();
+        let mut bots_rivals = DEFAULT_BOTS_RIVALS.to_owned();
+        let mut bots_teamwork = DEFAULT_BOTS_TEAMWORK.to_owned();
+        let mut lives = DEFAULT_LIVES;
+        let mut time_limit = DEFAULT_TIME_LIMIT;
+        let mut unsure_marks = DEFAULT_UNSURE_MARKS.to_owned();
         let mut address = "".to_owned();
 
         if let Some(page) = self.menu.fetch_page_mut(NEW_GAME) {
-            if let Some(v) = page.fetch_input_number_mut(FIELD_SIZE) {
-                field_size = utils::f64_to_u8_saturating_floor(v.value);
+            if let Some(v) = page.fetch_input_select_mut(BOARD) {
+                board = v.get_value().unwrap_or(DEFAULT_BOARD).to_owned();
+            }
+
+            if let Some(v) = page.fetch_input_number_mut(FIELD_WIDTH) {
+                field_width = utils::f64_to_u16_saturating_floor(v.value);
+            }
+
+            if let Some(v) = page.fetch_input_number_mut(FIELD_HEIGHT) {
+                field_height = utils::f64_to_u16_saturating_floor(v.value);
+            }
+
+            if let Some(v) = page.fetch_input_select_mut(GRID) {
+                grid = v.get_value().unwrap_or(DEFAULT_GRID).to_owned();
+            }
+
+            if let Some(v) = page.fetch_input_select_mut(EDGES) {
+                edges = v.get_value().unwrap_or(DEFAULT_EDGES).to_owned();
+            }
+
+            if let Some(v) = page.fetch_input_select_mut(MINES_MODE) {
+                mines_mode = v.get_value().unwrap_or(DEFAULT_MINES_MODE).to_owned();
             }
 
             if let Some(v) = page.fetch_input_number_mut(MINES_DENSITY) {
                 mines_density = v.value;
             }
 
+            if let Some(v) = page.fetch_input_number_mut(MINES_COUNT) {
+                mines_count = utils::f64_to_u32_saturating_floor(v.value);
+            }
+
+            if let Some(v) = page.fetch_input_select_mut(GENERATOR) {
+                generator = v.get_value().unwrap_or(DEFAULT_GENERATOR).to_owned();
+            }
+
+            if let Some(v) = page.fetch_input_select_mut(OPENING) {
+                opening = v.get_value().unwrap_or(DEFAULT_OPENING).to_owned();
+            }
+
+            if let Some(v) = page.fetch_input_text_mut(SEED) {
+                seed = v.value.trim().to_owned();
+            }
+
+            if let Some(v) = page.fetch_input_text_mut(BOARD_FILE) {
+                board_file = v.value.trim().to_owned();
+            }
+
             if let Some(v) = page.fetch_input_number_mut(BOTS) {
                 bots = utils::f64_to_u8_saturating_floor(v.value);
             }
@@ -357,6 +786,26 @@ impl Application {
             if let Some(v) = page.fetch_input_number_mut(BOTS_REACTION) {
                 bots_reaction = v.value;
             }
+
+            if let Some(v) = page.fetch_input_select_mut(BOTS_GUESSING) {
+                bots_guessing = v.get_value().unwrap_or(DEFAULT_BOTS_GUESSING).to_owned();
+            }
+
+            if let Some(v) = page.fetch_input_select_mut(BOTS_RIVALS) {
+                bots_rivals = v.get_value().unwrap_or(DEFAULT_BOTS_RIVALS).to_owned();
+            }
+
+            if let Some(v) = page.fetch_input_select_mut(BOTS_TEAMWORK) {
+                bots_teamwork = v.get_value().unwrap_or(DEFAULT_BOTS_TEAMWORK).to_owned();
+            }
+
+            if let Some(v) = page.fetch_input_number_mut(LIVES) {
+                lives = utils::f64_to_u8_saturating_floor(v.value);
+            }
+
+            if let Some(v) = page.fetch_input_number_mut(TIME_LIMIT) {
+                time_limit = utils::f64_to_u32_saturating_floor(v.value);
+            }
         }
 
         if let Some(page) = self.menu.get_page_current_mut() {
@@ -371,32 +820,140 @@ impl Application {
             }
         }
 
+        if let Some(page) = self.menu.get_page_current_mut() {
+            if let Some(v) = page.fetch_input_select_mut(UNSURE_MARKS) {
+                unsure_marks = v.get_value().unwrap_or(DEFAULT_UNSURE_MARKS).to_owned();
+            }
+        }
+
         let address = address.parse().map_err(|e| format!("{}", e))?;
 
         if is_host {
-            let field = Field::new(field_size, mines_density);
+            let is_infinite = board == BOARD_INFINITE;
+
+            if is_infinite {
+                if mines_mode == MINES_MODE_COUNT {
+                    return Err("An infinite board needs the density mines mode.".to_owned());
+                }
+
+                field_width = INFINITE_FIELD_SIZE;
+                field_height = INFINITE_FIELD_SIZE;
+            }
+
+            let layout = if board_file.is_empty() {
+                None
+            } else if is_infinite {
+                return Err("A board file can't be used with an infinite board.".to_owned());
+            } else {
+                let layout = Board::load(Path::new(&board_file))?;
+                field_width = layout.get_width();
+                field_height = layout.get_height();
+                Some(layout)
+            };
+
+            let mines_amount = if mines_mode == MINES_MODE_COUNT {
+                MinesAmount::Count(mines_count)
+            } else {
+                MinesAmount::Density(mines_density)
+            };
+
+            let seed = if seed.is_empty() {
+                rand::random()
+            } else {
+                seed.parse()
+                    .map_err(|e| format!("Failed to parse the seed: {}", e))?
+            };
+
+            let mut field = Field::new(field_width, field_height, mines_amount, seed);
+
+            let is_wrapped = is_infinite || edges == EDGES_WRAPPED;
+
+            if grid == GRID_HEX {
+                if is_wrapped && field_height % 2 != 0 {
+                    return Err("A hex field with wrapped edges needs an even height.".to_owned());
+                }
+
+                field.set_topology(Box::new(HexTopology {}));
+            }
+
+            field.set_wrapped(is_wrapped);
+
+            if is_infinite {
+                field.set_generator(Generator::Chunked);
+            } else if generator == GENERATOR_NO_GUESS {
+                field.set_generator(Generator::NoGuess);
+            }
+
+            field.set_opening(match opening.as_str() {
+                OPENING_UNPROTECTED => Opening::Unprotected,
+                OPENING_SAFE_CELL => Opening::SafeCell,
+                OPENING_ZERO => Opening::Zero,
+                _ => Opening::SafeArea,
+            });
+
+            if let Some(layout) = layout {
+                layout.apply(&mut field);
+            }
 
             let mut sappers = Vec::with_capacity(usize::from(bots) + 1);
 
+            let bots_guessing = match bots_guessing.as_str() {
+                BOTS_GUESSING_NEVER => Guessing::Never,
+                BOTS_GUESSING_CAUTIOUS => Guessing::Cautious,
+                _ => Guessing::Always,
+            };
+
+            let bots_skills = self.read_bots_skills();
+
+            let bots_teamwork = if bots_teamwork == BOTS_TEAMWORK_COOPERATIVE {
+                Teamwork::Cooperative
+            } else {
+                Teamwork::Competitive
+            };
+
             for i in 0..bots {
-                sappers.push(Sapper::new(
+                let mut sapper = Sapper::new(
                     i,
                     SapperBehavior::Bot,
                     field.generate_random_position(),
                     bots_reaction,
-                ));
+                );
+
+                sapper.set_guessing(bots_guessing);
+                sapper.set_avoiding_rivals(bots_rivals == BOTS_RIVALS_AVOIDED);
+                sapper.set_teamwork(bots_teamwork);
+                sapper.set_skill(
+                    bots_skills
+                        .get(usize::from(i))
+                        .or_else(|| bots_skills.last())
+                        .copied()
+                        .unwrap_or(Skill::DEFAULT),
+                );
+                sappers.push(sapper);
             }
 
-            self.server = Some(block_on(Server::new(address, Game::new(field, sappers)))?);
+            let mut game = Game::new(field, sappers);
+            game.set_lives(lives);
+            game.set_time_limit(time_limit.saturating_mul(60));
+            game.set_scoring(self.read_scoring());
+
+            self.server = Some(block_on(Server::new(address, game))?);
         }
 
         self.client = None;
-        self.client = Some(block_on(Client::new(address))?);
+
+        let mut client = block_on(Client::new(address))?;
+        client.set_question_blocking(unsure_marks == UNSURE_MARKS_BLOCKING);
+        self.client = Some(client);
 
         if let Some(page) = self.menu.fetch_page_mut(MAIN) {
             if let Some(button) = page.fetch_button_mut(CONTINUE) {
                 button.is_active = true;
             }
+
+            if let Some(button) = page.fetch_button_mut(EXPORT) {
+                button.is_active = true;
+            }
         }
 
         return Ok(());
@@ -415,22 +972,106 @@ impl Application {
         }
     }
 
+    // Prefers the server's game when hosting since only it knows where the hidden mines are, but
+    // only the client knows which of its sappers is the player
+    fn export_game(&mut self) {
+        let player = self
+            .client
+            .as_ref()
+            .and_then(|c| c.game.get_player())
+            .map(Sapper::get_id);
+
+        let game = match (self.server.as_ref(), self.client.as_ref()) {
+            (Some(server), _) => &server.game,
+            (None, Some(client)) => &client.game,
+            (None, None) => return,
+        };
+
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        let path = format!("{}-{}", env!("CARGO_PKG_NAME"), time);
+
+        match export::save(game, player, Path::new(&path)) {
+            Ok(saved) => {
+                self.menu
+                    .show_message(format!("Saved to {}", saved.join(" and ")), EXPORT, BACK);
+            }
+            Err(error) => {
+                self.menu.show_message(error, ERROR, BACK);
+            }
+        }
+    }
+
     fn reset_settings(&mut self) {
         if let Some(page) = self.menu.fetch_page_mut(NEW_GAME) {
-            if let Some(v) = page.fetch_input_number_mut(FIELD_SIZE) {
-                v.value = f64::from(DEFAULT_FILED_SIZE);
+            if let Some(v) = page.fetch_input_select_mut(PROFILE) {
+                v.select(DEFAULT_PROFILE);
+            }
+
+            if let Some(v) = page.fetch_input_text_mut(PROFILE_NAME) {
+                v.value.clear();
+            }
+
+            if let Some(v) = page.fetch_input_select_mut(BOARD) {
+                v.select(DEFAULT_BOARD);
+            }
+
+            if let Some(v) = page.fetch_input_number_mut(FIELD_WIDTH) {
+                v.value = f64::from(DEFAULT_FIELD_WIDTH);
+            }
+
+            if let Some(v) = page.fetch_input_number_mut(FIELD_HEIGHT) {
+                v.value = f64::from(DEFAULT_FIELD_HEIGHT);
+            }
+
+            if let Some(v) = page.fetch_input_select_mut(GRID) {
+                v.select(DEFAULT_GRID);
+            }
+
+            if let Some(v) = page.fetch_input_select_mut(EDGES) {
+                v.select(DEFAULT_EDGES);
+            }
+
+            if let Some(v) = page.fetch_input_select_mut(MINES_MODE) {
+                v.select(DEFAULT_MINES_MODE);
             }
 
             if let Some(v) = page.fetch_input_number_mut(MINES_DENSITY) {
                 v.value = DEFAULT_MINES_DENSITY;
             }
 
-            if let Some(v) = page.fetch_input_number_mut(BOTS) {
-                v.value = f64::from(DEFAULT_BOTS);
+            if let Some(v) = page.fetch_input_number_mut(MINES_COUNT) {
+                v.value = f64::from(DEFAULT_MINES_COUNT);
             }
 
-            if let Some(v) = page.fetch_input_number_mut(BOTS_REACTION) {
-                v.value = DEFAULT_BOTS_REACTION;
+            if let Some(v) = page.fetch_input_select_mut(GENERATOR) {
+                v.select(DEFAULT_GENERATOR);
+            }
+
+            if let Some(v) = page.fetch_input_select_mut(OPENING) {
+                v.select(DEFAULT_OPENING);
+            }
+
+            if let Some(v) = page.fetch_input_text_mut(SEED) {
+                v.value.clear();
+            }
+
+            if let Some(v) = page.fetch_input_text_mut(BOARD_FILE) {
+                v.value.clear();
+            }
+
+            if let Some(v) = page.fetch_input_number_mut(LIVES) {
+                v.value = f64::from(DEFAULT_LIVES);
+            }
+
+            if let Some(v) = page.fetch_input_number_mut(TIME_LIMIT) {
+                v.value = f64::from(DEFAULT_TIME_LIMIT);
+            }
+
+            if let Some(v) = page.fetch_input_select_mut(UNSURE_MARKS) {
+                v.select(DEFAULT_UNSURE_MARKS);
             }
 
             if let Some(v) = page.fetch_input_text_mut(SERVER_IP) {
@@ -441,8 +1082,288 @@ impl Application {
                 v.value = DEFAULT_SERVER_PORT.to_owned();
             }
 
+            self.profile_applied = DEFAULT_PROFILE.to_owned();
             self.set_screen_update(ScreenUpdate::Partial);
         }
+
+        self.reset_bots();
+        self.reset_scoring();
+    }
+
+    fn reset_bots(&mut self) {
+        if let Some(page) = self.menu.fetch_page_mut(NEW_GAME) {
+            if let Some(v) = page.fetch_input_number_mut(BOTS) {
+                v.value = f64::from(DEFAULT_BOTS);
+            }
+
+            if let Some(v) = page.fetch_input_number_mut(BOTS_REACTION) {
+                v.value = DEFAULT_BOTS_REACTION;
+            }
+
+            if let Some(v) = page.fetch_input_select_mut(BOTS_GUESSING) {
+                v.select(DEFAULT_BOTS_GUESSING);
+            }
+
+            if let Some(v) = page.fetch_input_select_mut(BOTS_RIVALS) {
+                v.select(DEFAULT_BOTS_RIVALS);
+            }
+
+            if let Some(v) = page.fetch_input_select_mut(BOTS_TEAMWORK) {
+                v.select(DEFAULT_BOTS_TEAMWORK);
+            }
+        }
+
+        if let Some(page) = self.menu.fetch_page_mut(BOTS_SKILLS) {
+            for label in BOT_SKILLS {
+                if let Some(v) = page.fetch_input_select_mut(label) {
+                    v.select(Skill::DEFAULT.get_name());
+                }
+            }
+        }
+    }
+
+    fn reset_scoring(&mut self) {
+        if let Some(page) = self.menu.fetch_page_mut(SCORING) {
+            if let Some(v) = page.fetch_input_number_mut(SCORING_DISCOVERY) {
+                v.value = f64::from(DEFAULT_SCORING.discovery);
+            }
+
+            if let Some(v) = page.fetch_input_number_mut(SCORING_CELL) {
+                v.value = f64::from(DEFAULT_SCORING.cell);
+            }
+
+            if let Some(v) = page.fetch_input_number_mut(SCORING_FLAG_BONUS) {
+                v.value = f64::from(DEFAULT_SCORING.flag_bonus);
+            }
+
+            if let Some(v) = page.fetch_input_number_mut(SCORING_FLAG_PENALTY) {
+                v.value = f64::from(DEFAULT_SCORING.flag_penalty);
+            }
+
+            if let Some(v) = page.fetch_input_number_mut(SCORING_EXPLOSION) {
+                v.value = f64::from(DEFAULT_SCORING.explosion_penalty);
+            }
+
+            if let Some(v) = page.fetch_input_number_mut(SCORING_CLEAR_BONUS) {
+                v.value = f64::from(DEFAULT_SCORING.clear_bonus);
+            }
+        }
+    }
+
+    // Applies a newly chosen profile to the settings, or falls back to the custom profile once
+    // any of its settings is changed by hand
+    fn sync_profile(&mut self) {
+        let page = match self.menu.fetch_page_mut(NEW_GAME) {
+            Some(page) => page,
+            None => return,
+        };
+
+        let selected = page
+            .fetch_input_select_mut(PROFILE)
+            .and_then(|v| v.get_value())
+            .unwrap_or(DEFAULT_PROFILE)
+            .to_owned();
+
+        let profile = self.profiles.iter().find(|p| p.name == selected);
+
+        if selected != self.profile_applied {
+            if let Some(profile) = profile {
+                Self::write_profile(page, profile);
+            }
+
+            self.profile_applied = selected;
+        } else if let Some(profile) = profile {
+            if !Self::read_profile(page).is_same(profile) {
+                if let Some(v) = page.fetch_input_select_mut(PROFILE) {
+                    v.select(profile::CUSTOM);
+                }
+
+                self.profile_applied = profile::CUSTOM.to_owned();
+            }
+        }
+    }
+
+    fn save_profile(&mut self) -> Result<(), String> {
+        let page = match self.menu.fetch_page_mut(NEW_GAME) {
+            Some(page) => page,
+            None => return Ok(()),
+        };
+
+        let name = page
+            .fetch_input_text_mut(PROFILE_NAME)
+            .map(|v| v.value.trim().to_owned())
+            .unwrap_or_default();
+
+        if name.is_empty() {
+            return Err("Enter a profile name to save the settings under.".to_owned());
+        }
+
+        if name == profile::CUSTOM || profile::get_builtin().iter().any(|p| p.name == name) {
+            return Err(format!("The {} profile can't be overwritten.", name));
+        }
+
+        let mut profile = Self::read_profile(page);
+        profile.name = name.clone();
+
+        if let Some(existing) = self.profiles.iter_mut().find(|p| p.name == name) {
+            *existing = profile;
+        } else {
+            self.profiles.push(profile);
+
+            if let Some(v) = page.fetch_input_select_mut(PROFILE) {
+                v.options.push(name.clone());
+            }
+        }
+
+        if let Some(v) = page.fetch_input_select_mut(PROFILE) {
+            v.select(&name);
+        }
+
+        self.profile_applied = name;
+        self.set_screen_update(ScreenUpdate::Partial);
+
+        let builtin = profile::get_builtin();
+
+        let user = self
+            .profiles
+            .iter()
+            .filter(|p| !builtin.iter().any(|b| b.name == p.name))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        return profile::save(Path::new(PROFILES_FILE), &user);
+    }
+
+    // The skill of every bot slot in order, the last one goes for the rest of the bots
+    fn read_bots_skills(&mut self) -> Vec<Skill> {
+        let mut skills = Vec::with_capacity(BOT_SKILLS.len());
+
+        if let Some(page) = self.menu.fetch_page_mut(BOTS_SKILLS) {
+            for label in BOT_SKILLS {
+                if let Some(v) = page.fetch_input_select_mut(label) {
+                    skills.push(
+                        v.get_value()
+                            .and_then(Skill::from_name)
+                            .unwrap_or(Skill::DEFAULT),
+                    );
+                }
+            }
+        }
+
+        return skills;
+    }
+
+    fn read_scoring(&mut self) -> Scoring {
+        let mut scoring = DEFAULT_SCORING;
+
+        if let Some(page) = self.menu.fetch_page_mut(SCORING) {
+            if let Some(v) = page.fetch_input_number_mut(SCORING_DISCOVERY) {
+                scoring.discovery = utils::f64_to_u16_saturating_floor(v.value);
+            }
+
+            if let Some(v) = page.fetch_input_number_mut(SCORING_CELL) {
+                scoring.cell = utils::f64_to_u16_saturating_floor(v.value);
+            }
+
+            if let Some(v) = page.fetch_input_number_mut(SCORING_FLAG_BONUS) {
+                scoring.flag_bonus = utils::f64_to_u16_saturating_floor(v.value);
+            }
+
+            if let Some(v) = page.fetch_input_number_mut(SCORING_FLAG_PENALTY) {
+                scoring.flag_penalty = utils::f64_to_u16_saturating_floor(v.value);
+            }
+
+            if let Some(v) = page.fetch_input_number_mut(SCORING_EXPLOSION) {
+                scoring.explosion_penalty = utils::f64_to_u16_saturating_floor(v.value);
+            }
+
+            if let Some(v) = page.fetch_input_number_mut(SCORING_CLEAR_BONUS) {
+                scoring.clear_bonus = utils::f64_to_u16_saturating_floor(v.value);
+            }
+        }
+
+        return scoring;
+    }
+
+    fn read_profile(page: &mut Page) -> Profile {
+        let mut profile = Profile {
+            name: profile::CUSTOM.to_owned(),
+            width: DEFAULT_FIELD_WIDTH,
+            height: DEFAULT_FIELD_HEIGHT,
+            mines_amount: MinesAmount::Density(DEFAULT_MINES_DENSITY),
+            bots: DEFAULT_BOTS,
+            bots_reaction: DEFAULT_BOTS_REACTION,
+        };
+
+        if let Some(v) = page.fetch_input_number_mut(FIELD_WIDTH) {
+            profile.width = utils::f64_to_u16_saturating_floor(v.value);
+        }
+
+        if let Some(v) = page.fetch_input_number_mut(FIELD_HEIGHT) {
+            profile.height = utils::f64_to_u16_saturating_floor(v.value);
+        }
+
+        let is_count = page
+            .fetch_input_select_mut(MINES_MODE)
+            .map_or(false, |v| v.get_value() == Some(MINES_MODE_COUNT));
+
+        if is_count {
+            if let Some(v) = page.fetch_input_number_mut(MINES_COUNT) {
+                profile.mines_amount =
+                    MinesAmount::Count(utils::f64_to_u32_saturating_floor(v.value));
+            }
+        } else if let Some(v) = page.fetch_input_number_mut(MINES_DENSITY) {
+            profile.mines_amount = MinesAmount::Density(v.value);
+        }
+
+        if let Some(v) = page.fetch_input_number_mut(BOTS) {
+            profile.bots = utils::f64_to_u8_saturating_floor(v.value);
+        }
+
+        if let Some(v) = page.fetch_input_number_mut(BOTS_REACTION) {
+            profile.bots_reaction = v.value;
+        }
+
+        return profile;
+    }
+
+    fn write_profile(page: &mut Page, profile: &Profile) {
+        if let Some(v) = page.fetch_input_number_mut(FIELD_WIDTH) {
+            v.value = f64::from(profile.width);
+        }
+
+        if let Some(v) = page.fetch_input_number_mut(FIELD_HEIGHT) {
+            v.value = f64::from(profile.height);
+        }
+
+        match profile.mines_amount {
+            MinesAmount::Count(count) => {
+                if let Some(v) = page.fetch_input_select_mut(MINES_MODE) {
+                    v.select(MINES_MODE_COUNT);
+                }
+
+                if let Some(v) = page.fetch_input_number_mut(MINES_COUNT) {
+                    v.value = f64::from(count);
+                }
+            }
+            MinesAmount::Density(density) => {
+                if let Some(v) = page.fetch_input_select_mut(MINES_MODE) {
+                    v.select(MINES_MODE_DENSITY);
+                }
+
+                if let Some(v) = page.fetch_input_number_mut(MINES_DENSITY) {
+                    v.value = density;
+                }
+            }
+        }
+
+        if let Some(v) = page.fetch_input_number_mut(BOTS) {
+            v.value = f64::from(profile.bots);
+        }
+
+        if let Some(v) = page.fetch_input_number_mut(BOTS_REACTION) {
+            v.value = profile.bots_reaction;
+        }
     }
 
     fn toggle_menu(&mut self) {
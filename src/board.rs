@@ -0,0 +1,201 @@
+use crate::event::EventManager;
+use crate::field::DiscoveryResult;
+use crate::field::Field;
+use std::collections::HashSet;
+use std::path::Path;
+
+const TEXT_MINE: char = '*';
+const TEXT_SAFE: char = '.';
+const TEXT_REVEALED: char = 'o';
+const TEXT_EXPLODED: char = '#';
+const MBF_EXTENSION: &str = "mbf";
+
+// A predefined mine layout, optionally with cells which are discovered from the start
+pub struct Board {
+    width: u16,
+    height: u16,
+    mines: HashSet<u32>,
+    revealed: Vec<u32>,
+}
+
+impl Board {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let data = std::fs::read(path)
+            .map_err(|e| format!("Failed to read the board file ({}): {}", path.display(), e))?;
+
+        if path.extension().map_or(false, |e| e == MBF_EXTENSION) {
+            return Self::parse_mbf(&data);
+        } else {
+            let text = String::from_utf8(data).map_err(|e| format!("{}", e))?;
+            return Self::parse_text(&text);
+        }
+    }
+
+    // Every line is a row of the field, where `*` is a mine, `.` is a safe cell and `o` is a safe
+    // cell which is discovered from the start. Digits and `#` of an exported field are read as
+    // discovered cells and mines too. The board ends with the first empty line after it
+    pub fn parse_text(text: &str) -> Result<Self, String> {
+        let mut width = None;
+        let mut height = 0;
+        let mut mines = HashSet::new();
+        let mut revealed = Vec::new();
+        let mut position = 0;
+
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() {
+                if height == 0 {
+                    continue;
+                } else {
+                    break;
+                }
+            }
+
+            let line_width = line.chars().count();
+
+            if *width.get_or_insert(line_width) != line_width {
+                return Err(format!(
+                    "Row {} of the board has a different width.",
+                    height + 1
+                ));
+            }
+
+            for symbol in line.chars() {
+                match symbol {
+                    TEXT_MINE | TEXT_EXPLODED => {
+                        mines.insert(position);
+                    }
+                    TEXT_SAFE => {}
+                    TEXT_REVEALED | '0'..='8' => {
+                        revealed.push(position);
+                    }
+                    _ => {
+                        return Err(format!("Unknown board symbol `{}`.", symbol));
+                    }
+                }
+
+                position += 1;
+            }
+
+            height += 1;
+        }
+
+        let width = u16::try_from(width.unwrap_or(0))
+            .map_err(|e| format!("The board is too wide: {}", e))?;
+        let height = u16::try_from(height).map_err(|e| format!("The board is too high: {}", e))?;
+
+        return Self::new(width, height, mines, revealed);
+    }
+
+    // The MBF format starts with one byte of width, one byte of height and two big endian bytes
+    // of the mines count, followed by a pair of x and y bytes for every mine
+    pub fn parse_mbf(data: &[u8]) -> Result<Self, String> {
+        let (width, height, count, body) = match data {
+            [width, height, count_high, count_low, body @ ..] => (
+                *width,
+                *height,
+                usize::from(u16::from_be_bytes([*count_high, *count_low])),
+                body,
+            ),
+            _ => return Err("The board file is too short.".to_owned()),
+        };
+
+        if body.len() < count * 2 {
+            return Err("The board file has fewer mines than it declares.".to_owned());
+        }
+
+        let mut mines = HashSet::with_capacity(count);
+
+        for mine in body.chunks_exact(2).take(count) {
+            let (x, y) = match *mine {
+                [x, y] => (x, y),
+                _ => continue,
+            };
+
+            if x >= width || y >= height {
+                return Err(format!("The mine at {}:{} is out of the board.", x, y));
+            }
+
+            mines.insert(u32::from(y) * u32::from(width) + u32::from(x));
+        }
+
+        return Self::new(u16::from(width), u16::from(height), mines, Vec::new());
+    }
+
+    fn new(
+        width: u16,
+        height: u16,
+        mines: HashSet<u32>,
+        revealed: Vec<u32>,
+    ) -> Result<Self, String> {
+        if width == 0 || height == 0 {
+            return Err("The board is empty.".to_owned());
+        }
+
+        return Ok(Self {
+            width,
+            height,
+            mines,
+            revealed,
+        });
+    }
+
+    // Places the mines to the field and discovers the pre-revealed cells
+    pub fn apply(&self, field: &mut Field) {
+        field.set_mines(self.mines.clone());
+
+        let mut events = EventManager::new();
+
+        for position in &self.revealed {
+            if let DiscoveryResult::Failure = field.discover(*position, &mut events) {
+                log::warn!("A pre-revealed cell {} of the board is mined", position);
+            }
+        }
+    }
+
+    pub const fn get_width(&self) -> u16 {
+        return self.width;
+    }
+
+    pub const fn get_height(&self) -> u16 {
+        return self.height;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::MinesAmount;
+
+    #[test]
+    fn test_parse_text() {
+        let board = Board::parse_text("*..\n.o.\n..*\n").unwrap();
+        assert_eq!((board.get_width(), board.get_height()), (3, 3), "Size");
+        assert_eq!(board.mines.len(), 2, "Mines");
+
+        let mut field = Field::new(3, 3, MinesAmount::Count(0), 0);
+        board.apply(&mut field);
+        assert!(field.is_generated(), "Generated");
+        assert_eq!(field.get_mines_count(), 2, "Mines applied");
+        assert_eq!(field.get_cells_discovered_count(), 1, "Revealed");
+        assert_eq!(field.get_cell(4).unwrap().mines_around, Some(2), "Counted");
+
+        assert!(Board::parse_text("*..\n..\n").is_err(), "Uneven");
+        assert!(Board::parse_text("*x.\n").is_err(), "Unknown symbol");
+        assert!(Board::parse_text("\n").is_err(), "Empty");
+    }
+
+    #[test]
+    fn test_parse_mbf() {
+        let board = Board::parse_mbf(&[4, 2, 0, 2, 0, 0, 3, 1]).unwrap();
+        assert_eq!((board.get_width(), board.get_height()), (4, 2), "Size");
+        assert!(board.mines.contains(&0), "First mine");
+        assert!(board.mines.contains(&7), "Second mine");
+
+        assert!(Board::parse_mbf(&[4, 2, 0]).is_err(), "No header");
+        assert!(Board::parse_mbf(&[4, 2, 0, 2, 0, 0]).is_err(), "Too short");
+        assert!(
+            Board::parse_mbf(&[4, 2, 0, 1, 4, 0]).is_err(),
+            "Out of board"
+        );
+    }
+}
@@ -1,9 +1,16 @@
 use termwiz::color::AnsiColor;
 use termwiz::color::ColorAttribute;
 
+const MARK_NONE_ID: u8 = 0;
+const MARK_FLAG_ID: u8 = 1;
+const MARK_QUESTION_ID: u8 = 2;
+
+// Every mine is exploded once the game is over, so the ones a sapper has actually hit are told
+// apart by `is_hit`
 pub struct Cell {
     pub mines_around: Option<u8>,
     pub is_exploded: bool,
+    pub is_hit: bool,
 }
 
 impl Cell {
@@ -11,6 +18,7 @@ impl Cell {
         return Self {
             mines_around: None,
             is_exploded: false,
+            is_hit: false,
         };
     }
 
@@ -22,14 +30,17 @@ impl Cell {
         return !self.is_discovered() && !self.is_exploded;
     }
 
-    pub fn get_mark(&self, is_marked: bool) -> CellMark {
+    pub fn get_mark(&self, mark: Option<Mark>) -> CellMark {
         let symbol;
         let mut foreground = ColorAttribute::Default;
         let mut background = ColorAttribute::Default;
 
-        if is_marked {
+        if mark == Some(Mark::Flag) {
             symbol = '!';
             background = AnsiColor::Maroon.into();
+        } else if mark == Some(Mark::Question) {
+            symbol = '?';
+            background = AnsiColor::Olive.into();
         } else if self.is_exploded {
             symbol = '#';
             background = AnsiColor::Maroon.into();
@@ -67,3 +78,55 @@ pub struct CellMark {
     pub foreground: ColorAttribute,
     pub background: ColorAttribute,
 }
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Mark {
+    Flag,
+    Question,
+}
+
+impl Mark {
+    // Cycles through a flag, a question and no mark
+    pub const fn next(mark: Option<Self>) -> Option<Self> {
+        return match mark {
+            None => Some(Self::Flag),
+            Some(Self::Flag) => Some(Self::Question),
+            Some(Self::Question) => None,
+        };
+    }
+
+    pub const fn to_id(mark: Option<Self>) -> u8 {
+        return match mark {
+            None => MARK_NONE_ID,
+            Some(Self::Flag) => MARK_FLAG_ID,
+            Some(Self::Question) => MARK_QUESTION_ID,
+        };
+    }
+
+    pub const fn from_id(id: u8) -> Option<Self> {
+        return match id {
+            MARK_FLAG_ID => Some(Self::Flag),
+            MARK_QUESTION_ID => Some(Self::Question),
+            _ => None,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mark() {
+        assert!(Mark::next(None) == Some(Mark::Flag), "Flag");
+        assert!(
+            Mark::next(Some(Mark::Flag)) == Some(Mark::Question),
+            "Question"
+        );
+        assert!(Mark::next(Some(Mark::Question)).is_none(), "None");
+
+        for mark in [None, Some(Mark::Flag), Some(Mark::Question)] {
+            assert!(Mark::from_id(Mark::to_id(mark)) == mark, "Round trip");
+        }
+    }
+}
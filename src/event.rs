@@ -2,7 +2,7 @@ use serde::Deserialize;
 use serde::Serialize;
 use std::net::SocketAddr;
 
-pub const EVENT_SIZE: usize = 4; // (8 + data[16]) / 8
+pub const EVENT_SIZE: usize = 7; // (8 + data[48]) / 8
 
 // TODO: Remove clone drive
 #[derive(Clone)]
@@ -16,15 +16,77 @@ pub struct Event {
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 pub enum EventData {
     SapperConnect,
-    SapperConnectResponse { id: u8 },
-    SapperSpawn { id: u8, position: u16 },
-    SapperMove { id: u8, position: u16 },
-    SapperDiscover { id: u8, position: u16 },
-    SapperScore { id: u8, score: u16 },
-    SapperDie { id: u8 },
-    FieldCreate { size: u8 },
-    CellDiscover { position: u16, mines_around: u8 },
-    CellExplode { position: u16 },
+    SapperConnectResponse {
+        id: u8,
+    },
+    SapperSpawn {
+        id: u8,
+        position: u32,
+    },
+    SapperMove {
+        id: u8,
+        position: u32,
+    },
+    SapperDiscover {
+        id: u8,
+        position: u32,
+    },
+    SapperMark {
+        id: u8,
+        position: u32,
+        mark: u8,
+    },
+    SapperChord {
+        id: u8,
+        position: u32,
+    },
+    SapperScore {
+        id: u8,
+        score: u32,
+    },
+    SapperLives {
+        id: u8,
+        lives: u8,
+    },
+    SapperDie {
+        id: u8,
+    },
+    SapperResult {
+        id: u8,
+        cells: u32,
+        mistakes: u8,
+    },
+    SapperTime {
+        id: u8,
+        time: u32,
+    },
+    FieldCreate {
+        width: u16,
+        height: u16,
+        topology: u8,
+        is_wrapped: bool,
+    },
+    FieldMines {
+        count: u32,
+    },
+    FieldSeed {
+        seed: u32,
+    },
+    CellDiscover {
+        position: u32,
+        mines_around: u8,
+    },
+    CellExplode {
+        position: u32,
+    },
+    GameTime {
+        time: u32,
+        limit: u16,
+    },
+    GameOver {
+        state: u8,
+        winner: u8,
+    },
 }
 
 impl EventData {
@@ -99,4 +161,65 @@ impl EventManager {
     pub fn pop(&mut self) -> Option<Event> {
         return self.events.pop();
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Event> {
+        return self.events.iter();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_large_values() {
+        let create = EventData::FieldCreate {
+            width: u16::MAX,
+            height: u16::MAX,
+            topology: 1,
+            is_wrapped: true,
+        };
+
+        let discover = EventData::CellDiscover {
+            position: u32::MAX,
+            mines_around: 8,
+        };
+
+        let score = EventData::SapperScore {
+            id: u8::MAX,
+            score: u32::MAX,
+        };
+
+        let result = EventData::SapperResult {
+            id: u8::MAX,
+            cells: u32::MAX,
+            mistakes: u8::MAX,
+        };
+
+        assert_eq!(create.encode().len(), EVENT_SIZE);
+        assert_eq!(discover.encode().len(), EVENT_SIZE);
+        assert_eq!(score.encode().len(), EVENT_SIZE);
+        assert_eq!(result.encode().len(), EVENT_SIZE);
+
+        assert!(matches!(
+            EventData::decode(&create.encode()),
+            EventData::FieldCreate {
+                width: u16::MAX,
+                height: u16::MAX,
+                topology: 1,
+                is_wrapped: true,
+            }
+        ));
+
+        assert!(matches!(
+            EventData::decode(&discover.encode()),
+            EventData::CellDiscover {
+                position: u32::MAX,
+                mines_around: 8,
+            }
+        ));
+
+        assert_eq!(EventData::decode(&score.encode()), score);
+        assert_eq!(EventData::decode(&result.encode()), result);
+    }
 }
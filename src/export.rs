@@ -0,0 +1,221 @@
+use crate::cell::Cell;
+use crate::cell::Mark;
+use crate::game::Game;
+use crate::sapper::NAME_PLAYER;
+use serde::Serialize;
+use std::path::Path;
+
+const EXPORT_CELLS_MAX: usize = 1 << 22;
+const SYMBOL_MINE: char = '*';
+const SYMBOL_EXPLODED: char = '#';
+const SYMBOL_UNKNOWN: char = '.';
+
+#[derive(Serialize)]
+struct FieldExport {
+    width: u16,
+    height: u16,
+    topology: u8,
+    is_wrapped: bool,
+    seed: u32,
+    player: Option<u8>,
+    rows: Vec<String>,
+    sappers: Vec<SapperExport>,
+}
+
+#[derive(Serialize)]
+struct SapperExport {
+    id: u8,
+    name: &'static str,
+    position: (u16, u16),
+    score: u32,
+    is_alive: bool,
+    flags: Vec<(u16, u16)>,
+    questions: Vec<(u16, u16)>,
+}
+
+// Every cell is exported as a single symbol, so the grid can be loaded back as a board file:
+// a digit for a discovered cell, `#` for a mine hit by a sapper, `*` for any other mine and `.`
+// for the rest. A client doesn't know hidden mines, so it exports them as `.` too. The sapper of
+// the given player is named as the player, even though the server sees everyone as remote
+pub fn to_text(game: &Game, player: Option<u8>) -> Result<String, String> {
+    let export = collect(game, player)?;
+    let mut text = String::new();
+
+    for row in &export.rows {
+        text.push_str(row);
+        text.push('\n');
+    }
+
+    for sapper in &export.sappers {
+        text.push_str(&format!(
+            "\nsapper {} {} at {}:{} score {} {}\n",
+            sapper.id,
+            sapper.name,
+            sapper.position.0,
+            sapper.position.1,
+            sapper.score,
+            if sapper.is_alive {
+                "alive"
+            } else {
+                "dead"
+            },
+        ));
+
+        for (x, y) in &sapper.flags {
+            text.push_str(&format!("flag {}:{}\n", x, y));
+        }
+
+        for (x, y) in &sapper.questions {
+            text.push_str(&format!("question {}:{}\n", x, y));
+        }
+    }
+
+    return Ok(text);
+}
+
+pub fn to_json(game: &Game, player: Option<u8>) -> Result<String, String> {
+    return serde_json::to_string_pretty(&collect(game, player)?).map_err(|e| format!("{}", e));
+}
+
+// Saves both formats next to each other, returns the paths of the written files
+pub fn save(game: &Game, player: Option<u8>, path: &Path) -> Result<Vec<String>, String> {
+    let mut saved = Vec::with_capacity(2);
+
+    for (extension, data) in [
+        ("txt", to_text(game, player)?),
+        ("json", to_json(game, player)?),
+    ] {
+        let path = path.with_extension(extension);
+
+        std::fs::write(&path, data)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+        saved.push(path.display().to_string());
+    }
+
+    return Ok(saved);
+}
+
+fn collect(game: &Game, player: Option<u8>) -> Result<FieldExport, String> {
+    let field = &game.field;
+
+    if field.get_cells_count() > EXPORT_CELLS_MAX {
+        return Err("The field is too large to export.".to_owned());
+    }
+
+    let mut rows = Vec::with_capacity(usize::from(field.get_height()));
+
+    for y in 0..field.get_height() {
+        let mut row = String::with_capacity(usize::from(field.get_width()));
+
+        for x in 0..field.get_width() {
+            let position = u32::from(y) * u32::from(field.get_width()) + u32::from(x);
+
+            row.push(match field.get_cell(position) {
+                Some(cell) => to_symbol(cell, field.is_mined(position)),
+                None => SYMBOL_UNKNOWN,
+            });
+        }
+
+        rows.push(row);
+    }
+
+    let mut sappers = Vec::with_capacity(game.sappers.len());
+
+    for sapper in &game.sappers {
+        let mut flags = Vec::new();
+        let mut questions = Vec::new();
+
+        for (position, mark) in sapper.iter_marks() {
+            match mark {
+                Mark::Flag => flags.push(field.to_coordinate(position)),
+                Mark::Question => questions.push(field.to_coordinate(position)),
+            }
+        }
+
+        flags.sort_unstable();
+        questions.sort_unstable();
+
+        sappers.push(SapperExport {
+            id: sapper.get_id(),
+            name: if player == Some(sapper.get_id()) {
+                NAME_PLAYER
+            } else {
+                sapper.get_name()
+            },
+            position: field.to_coordinate(sapper.get_position()),
+            score: sapper.get_score(),
+            is_alive: sapper.is_alive(),
+            flags,
+            questions,
+        });
+    }
+
+    return Ok(FieldExport {
+        width: field.get_width(),
+        height: field.get_height(),
+        topology: field.get_topology().get_id(),
+        is_wrapped: field.is_wrapped(),
+        seed: field.get_seed(),
+        player,
+        rows,
+        sappers,
+    });
+}
+
+fn to_symbol(cell: &Cell, is_mined: bool) -> char {
+    if cell.is_hit {
+        return SYMBOL_EXPLODED;
+    } else if let Some(mines_around) = cell.mines_around {
+        return std::char::from_digit(u32::from(mines_around), 10).unwrap_or(SYMBOL_UNKNOWN);
+    } else if is_mined || cell.is_exploded {
+        return SYMBOL_MINE;
+    } else {
+        return SYMBOL_UNKNOWN;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+    use crate::event::EventManager;
+    use crate::field::Field;
+    use crate::field::MinesAmount;
+    use crate::sapper::Sapper;
+    use crate::sapper::SapperBehavior;
+
+    #[test]
+    fn test_to_text() {
+        let board = Board::parse_text("*..\n...\n..*\n").unwrap();
+        let mut field = Field::new(3, 3, MinesAmount::Count(0), 0);
+        board.apply(&mut field);
+        field.discover(2, &mut EventManager::new());
+        field.discover(8, &mut EventManager::new());
+
+        let mut sapper = Sapper::new(0, SapperBehavior::Bot, 4, 0.0);
+        sapper.set_mark(0, Some(Mark::Flag));
+
+        let mut game = Game::new(field, vec![sapper]);
+        let text = to_text(&game, None).unwrap();
+
+        assert_eq!(
+            text, "*10\n.21\n..#\n\nsapper 0 BOT at 1:1 score 0 alive\nflag 0:0\n",
+            "Text"
+        );
+
+        let board = Board::parse_text(&text).unwrap();
+        assert_eq!(
+            (board.get_width(), board.get_height()),
+            (3, 3),
+            "Loads back"
+        );
+        assert!(to_json(&game, None).unwrap().contains("\"flags\""), "JSON");
+
+        // Once the game is over every mine is exploded, but only the hit one is told so
+        game.field.explode_mines();
+        let text = to_text(&game, Some(0)).unwrap();
+        assert!(text.starts_with("*10\n.21\n..#\n"), "Hit after the end");
+        assert!(text.contains("sapper 0 YOU"), "Player");
+    }
+}
@@ -2,71 +2,292 @@ use crate::cell::Cell;
 use crate::event::EventData;
 use crate::event::EventManager;
 use crate::sapper::Sapper;
+use crate::solver;
+use crate::solver::Knowledge;
+use crate::topology::Direction;
+use crate::topology::SquareTopology;
+use crate::topology::Topology;
 use crate::utils;
 use rand::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use termwiz::cell::AttributeChange;
 use termwiz::color::AnsiColor;
 use termwiz::color::ColorAttribute;
 use termwiz::surface::Change;
+use termwiz::surface::Position;
 use termwiz::surface::Surface;
 
-const SHIFTS: [i32; 3] = [-1, 0, 1];
+const SPAWNS_SEED_SALT: u64 = 0x5350_4157_4E53; // Keeps spawns independent from mines
+
+// Both bound a no-guess generation, the steps are the cells the solver may look through over all
+// the attempts. Counted rather than timed to keep seeds reproducible, and they are spent in about
+// a second on any field size
+const NO_GUESS_ATTEMPTS_MAX: usize = 200;
+const NO_GUESS_STEPS_MAX: usize = 20_000_000;
+
+const CHUNK_SIZE: u16 = 16;
+const CHUNK_AREA: usize = 256; // CHUNK_SIZE * CHUNK_SIZE
+
+// Stands for every cell of a chunk which hasn't been allocated yet
+static CELL_UNTOUCHED: Cell = Cell::new();
 
 pub struct Field {
-    size: u8,
-    cells: Vec<Cell>,
-    mines: HashSet<u16>,
-    mines_density: f64,
+    width: u16,
+    height: u16,
+    topology: Box<dyn Topology>,
+    is_wrapped: bool,
+    chunks: HashMap<u32, Vec<Cell>>,
+    mines: HashSet<u32>,
+    safe_positions: HashSet<u32>,
+    mines_amount: MinesAmount,
+    generator: Generator,
+    opening: Opening,
+    is_generated: bool,
+    seed: u32,
+    spawns_rng: StdRng,
     cells_discovered_count: usize,
 }
 
-impl Field {
-    pub fn new(size: u8, mines_density: f64) -> Self {
-        let size_full = usize::from(size).pow(2);
-        let mut cells = Vec::with_capacity(size_full);
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub enum MinesAmount {
+    Density(f64),
+    Count(u32),
+}
 
-        for _ in 0..size_full {
-            cells.push(Cell::new());
-        }
+#[derive(Clone, Copy)]
+pub enum Generator {
+    Random,
+    NoGuess,
+    Chunked,
+}
+
+#[derive(Clone, Copy)]
+pub enum Opening {
+    Unprotected,
+    SafeCell,
+    SafeArea,
+    Zero,
+}
 
+impl Field {
+    pub fn new(width: u16, height: u16, mines_amount: MinesAmount, seed: u32) -> Self {
         return Self {
-            size,
-            cells,
+            width,
+            height,
+            topology: Box::new(SquareTopology {}),
+            is_wrapped: false,
+            chunks: HashMap::new(),
             mines: HashSet::new(), // TODO: Optimize allocation
-            mines_density,
+            safe_positions: HashSet::new(),
+            mines_amount,
+            generator: Generator::Random,
+            opening: Opening::SafeArea,
+            is_generated: false,
+            seed,
+            spawns_rng: StdRng::seed_from_u64(u64::from(seed) ^ SPAWNS_SEED_SALT),
             cells_discovered_count: 0,
         };
     }
 
-    fn generate_mines(&mut self, excepting_position: u16) {
-        self.mines.clear(); // TODO: Optimize reallocation
+    pub fn generate_mines(&mut self, openings: &[u32], events: &mut EventManager) {
+        let excepting_positions = self.get_opening_positions(openings);
+
+        // A chunked field has no mines up front, every chunk rolls its own ones on first access
+        if let Generator::Chunked = self.generator {
+            self.safe_positions = excepting_positions.into_iter().collect();
+            self.is_generated = true;
+
+            let mut chunks = self.chunks.keys().copied().collect::<Vec<_>>();
+            chunks.sort_unstable();
+
+            for chunk in chunks {
+                self.roll_chunk_mines(chunk);
+            }
+
+            return;
+        }
+
+        let mut rng = StdRng::seed_from_u64(u64::from(self.seed));
+        let mut steps_left = NO_GUESS_STEPS_MAX;
+
+        for attempt in 1..=NO_GUESS_ATTEMPTS_MAX {
+            self.mines = self.roll_mines(&mut rng, &excepting_positions);
+
+            if let Generator::Random | Generator::Chunked = self.generator {
+                break;
+            }
+
+            if self.is_solvable(openings, &mut steps_left) {
+                break;
+            }
+
+            if attempt == NO_GUESS_ATTEMPTS_MAX || steps_left == 0 {
+                log::warn!(
+                    "Failed to generate a no-guess field in {} attempts, falling back to a random one",
+                    attempt,
+                );
+
+                break;
+            }
+        }
+
+        self.is_generated = true;
+
+        events.fire(
+            EventData::FieldMines {
+                count: u32::try_from(self.mines.len()).unwrap_or(u32::MAX),
+            },
+            None,
+            None,
+        );
+    }
+
+    fn get_opening_positions(&self, openings: &[u32]) -> Vec<u32> {
+        let mut positions = Vec::new();
+
+        for opening in openings {
+            match self.opening {
+                Opening::Unprotected => {}
+                Opening::SafeCell => {
+                    positions.push(*opening);
+                }
+                Opening::SafeArea => {
+                    positions.append(&mut self.around(*opening, true));
+                }
+                Opening::Zero => {
+                    for position in self.around(*opening, true) {
+                        positions.append(&mut self.around(position, true));
+                    }
+                }
+            }
+        }
+
+        return positions;
+    }
+
+    fn roll_mines(&self, rng: &mut StdRng, excepting_positions: &[u32]) -> HashSet<u32> {
+        let mut mines = HashSet::new();
+
+        match self.mines_amount {
+            MinesAmount::Density(density) => {
+                for position in 0..self.get_size_full() {
+                    if utils::is_chance(rng, density) && !excepting_positions.contains(&position) {
+                        mines.insert(position);
+                    }
+                }
+            }
+            MinesAmount::Count(count) => {
+                let positions = (0..self.get_size_full())
+                    .filter(|p| !excepting_positions.contains(p))
+                    .collect::<Vec<_>>();
+
+                for position in
+                    positions.choose_multiple(rng, utils::u32_to_usize_saturating(count))
+                {
+                    mines.insert(*position);
+                }
+            }
+        }
+
+        return mines;
+    }
+
+    fn roll_chunk_mines(&mut self, chunk: u32) {
+        let density = match self.mines_amount {
+            MinesAmount::Density(density) => density,
+            MinesAmount::Count(_) => return,
+        };
+
+        let mut rng = StdRng::seed_from_u64((u64::from(self.seed) << 32) | u64::from(chunk));
+
+        for i in 0..CHUNK_AREA {
+            let is_mined = utils::is_chance(&mut rng, density);
+
+            if let Some(position) = self.to_position(chunk, i) {
+                if is_mined && !self.safe_positions.contains(&position) {
+                    self.mines.insert(position);
+                }
+            }
+        }
+    }
+
+    // Every attempt and every round of the solver costs a step per cell of the field, the field
+    // isn't solvable once they run out
+    fn is_solvable(&self, openings: &[u32], steps_left: &mut usize) -> bool {
+        let cells_count = self.get_cells_count();
+
+        if !spend_steps(steps_left, cells_count) || openings.iter().any(|p| self.is_mined(*p)) {
+            return false;
+        }
+
+        let mut knowledge = vec![Knowledge::Unknown; cells_count];
+        let mut to_reveal = openings.to_vec();
+        let mut revealed = 0;
+
+        loop {
+            while let Some(position) = to_reveal.pop() {
+                if self.is_mined(position) {
+                    continue;
+                }
+
+                if let Some(item) = knowledge.get_mut(utils::u32_to_usize_saturating(position)) {
+                    if *item != Knowledge::Unknown {
+                        continue;
+                    }
+
+                    let mines_around = self.count_mines_around(position);
+                    *item = Knowledge::Safe(mines_around);
+                    revealed += 1;
+
+                    if mines_around == 0 {
+                        to_reveal.append(&mut self.around(position, false));
+                    }
+                }
+            }
+
+            if !spend_steps(steps_left, cells_count) {
+                return false;
+            }
 
-        let excepting_positions = self.around(excepting_position, true);
+            let solution = solver::solve(self, &knowledge, Some(self.mines.len()));
 
-        for position in 0..self.get_size_full() {
-            if utils::is_chance(self.mines_density) && !excepting_positions.contains(&position) {
-                self.mines.insert(position);
+            if solution.is_empty() {
+                break;
             }
+
+            for position in solution.mined {
+                if let Some(item) = knowledge.get_mut(utils::u32_to_usize_saturating(position)) {
+                    *item = Knowledge::Mined;
+                }
+            }
+
+            to_reveal.extend(solution.safe);
         }
+
+        return revealed + self.mines.len() == cells_count;
     }
 
     pub fn explode_mines(&mut self) {
-        for position in &self.mines {
-            if let Some(cell) = self.cells.get_mut(usize::from(*position)) {
+        for position in std::mem::take(&mut self.mines) {
+            if let Some(cell) = self.get_cell_mut(position) {
                 cell.is_exploded = true;
             }
         }
-
-        self.mines.clear();
     }
 
-    pub fn discover(&mut self, position: u16, events: &mut EventManager) -> DiscoveryResult {
-        if self.mines.is_empty() {
-            self.generate_mines(position);
+    pub fn discover(&mut self, position: u32, events: &mut EventManager) -> DiscoveryResult {
+        if !self.is_generated {
+            self.generate_mines(&[position], events);
         }
 
-        let cell = if let Some(cell) = self.cells.get_mut(usize::from(position)) {
+        self.touch(position);
+
+        let is_mined = self.is_mined(position);
+
+        let cell = if let Some(cell) = self.get_cell_mut(position) {
             cell
         } else {
             // TODO: Log error
@@ -75,17 +296,43 @@ impl Field {
 
         if cell.is_exploded || cell.is_discovered() {
             return DiscoveryResult::AlreadyDiscovered;
-        } else if self.mines.contains(&position) {
+        } else if is_mined {
             // TODO: Probably I should delete the mine here too
             cell.is_exploded = true;
+            cell.is_hit = true;
 
             events.fire(EventData::CellExplode { position }, None, None);
 
             return DiscoveryResult::Failure;
         } else {
-            let near_positions = self.around(position, false);
+            return DiscoveryResult::Success(self.reveal(position, events));
+        }
+    }
+
+    // Reveals a safe cell and floods through zero cells with an explicit stack, since a big
+    // empty region would be too deep for recursion
+    fn reveal(&mut self, position: u32, events: &mut EventManager) -> Vec<u32> {
+        let mut revealed = Vec::new();
+        let mut pending = vec![position];
+        let mut near_positions = Vec::with_capacity(9);
+
+        while let Some(position) = pending.pop() {
+            if self.is_mined(position)
+                || self
+                    .get_cell(position)
+                    .map_or(true, |c| c.is_discovered() || c.is_exploded)
+            {
+                continue;
+            }
+
+            self.around_into(position, false, &mut near_positions);
+
             let mut mines_around = 0;
 
+            for position_near in &near_positions {
+                self.touch(*position_near);
+            }
+
             for position_near in &near_positions {
                 if self.is_mined(*position_near) {
                     mines_around += 1;
@@ -93,11 +340,11 @@ impl Field {
             }
 
             if let Some(cell) = self.get_cell_mut(position) {
-                // TODO: Simplify, since I already should have `cell`
                 cell.mines_around = Some(mines_around);
             }
 
             self.cells_discovered_count += 1;
+            revealed.push(position);
 
             events.fire(
                 EventData::CellDiscover {
@@ -110,76 +357,119 @@ impl Field {
 
             if mines_around == 0 {
                 for position_near in &near_positions {
-                    self.discover(*position_near, events);
+                    if self
+                        .get_cell(*position_near)
+                        .map_or(false, |c| !c.is_discovered())
+                    {
+                        pending.push(*position_near);
+                    }
                 }
             }
-
-            return DiscoveryResult::Success;
         }
+
+        return revealed;
     }
 
     // TODO: Return stack-allocated array
-    pub fn around(&self, center: u16, include_center: bool) -> Vec<u16> {
+    pub fn around(&self, center: u32, include_center: bool) -> Vec<u32> {
         // TODO: Find a way to a call lambda while iteration instead of returning an array
         let mut positions = Vec::with_capacity(9);
+        self.around_into(center, include_center, &mut positions);
+        return positions;
+    }
 
-        for y in &SHIFTS {
-            for x in &SHIFTS {
-                let x = *x;
-                let y = *y;
+    pub fn around_into(&self, center: u32, include_center: bool, positions: &mut Vec<u32>) {
+        positions.clear();
 
-                if include_center || x != 0 || y != 0 {
-                    if let Some(moved) = self.move_position(center, x, y) {
-                        positions.push(moved);
-                    }
+        if include_center {
+            positions.push(center);
+        }
+
+        let (_, y) = self.to_coordinate(center);
+
+        for (shift_x, shift_y) in self.topology.get_neighbors(i32::from(y)) {
+            if let Some(moved) = self.move_position(center, *shift_x, *shift_y) {
+                // A small wrapped field may reach the same cell from several sides
+                if moved != center && !positions.contains(&moved) {
+                    positions.push(moved);
                 }
             }
         }
-
-        return positions;
     }
 
     // TODO: Try to optimize
-    pub fn move_position(&self, position: u16, shift_x: i32, shift_y: i32) -> Option<u16> {
-        let size = i32::from(self.size);
+    pub fn move_position(&self, position: u32, shift_x: i32, shift_y: i32) -> Option<u32> {
+        let width = i32::from(self.width);
+        let height = i32::from(self.height);
         let (x, y) = self.to_coordinate(position);
-        let x = i32::from(x) + shift_x;
-        let y = i32::from(y) + shift_y;
+        let mut x = i32::from(x) + shift_x;
+        let mut y = i32::from(y) + shift_y;
+
+        if self.is_wrapped && width > 0 && height > 0 {
+            x = x.rem_euclid(width);
+            y = y.rem_euclid(height);
+        }
 
-        if 0 <= x && x < size && 0 <= y && y < size {
-            return u16::try_from(size * y + x).ok();
+        if 0 <= x && x < width && 0 <= y && y < height {
+            return u32::try_from(i64::from(width) * i64::from(y) + i64::from(x)).ok();
         } else {
             return None;
         }
     }
 
+    pub fn step(&self, position: u32, direction: Direction) -> Option<u32> {
+        let (_, y) = self.to_coordinate(position);
+
+        return self
+            .topology
+            .get_step(direction, i32::from(y))
+            .and_then(|(x, y)| self.move_position(position, x, y));
+    }
+
     #[allow(clippy::integer_division)] // TODO: Try to resolve
-    pub fn to_coordinate(&self, position: u16) -> (u16, u16) {
+    pub fn to_coordinate(&self, position: u32) -> (u16, u16) {
+        let width = u32::from(self.width);
+
         return (
-            position % u16::from(self.size),
-            position / u16::from(self.size),
+            u16::try_from(position % width).unwrap_or(u16::MAX),
+            u16::try_from(position / width).unwrap_or(u16::MAX),
         );
     }
 
     // TODO: Try to optimize
-    pub fn to_distance(&self, p1: u16, p2: u16) -> u16 {
-        let (x1, y1) = self.to_coordinate(p1);
-        let (x2, y2) = self.to_coordinate(p2);
-        let distance_x = utils::difference(x1, x2);
-        let distance_y = utils::difference(y1, y2);
-        return distance_x + distance_y;
+    pub fn to_distance(&self, p1: u32, p2: u32) -> u32 {
+        let (_, y) = self.to_coordinate(p1);
+        let (shift_x, shift_y) = self.to_shift(p1, p2);
+        return self.topology.to_distance(i32::from(y), shift_x, shift_y);
     }
 
-    pub fn generate_random_position(&self) -> u16 {
-        return rand::thread_rng().gen_range(0, self.get_size_full());
+    // Returns the shortest shift which moves from the first position to the second one
+    pub fn to_shift(&self, from: u32, to: u32) -> (i32, i32) {
+        let (x1, y1) = self.to_coordinate(from);
+        let (x2, y2) = self.to_coordinate(to);
+        let mut shift_x = i32::from(x2) - i32::from(x1);
+        let mut shift_y = i32::from(y2) - i32::from(y1);
+
+        if self.is_wrapped {
+            shift_x = utils::wrap_shift(shift_x, i32::from(self.width));
+            shift_y = utils::wrap_shift(shift_y, i32::from(self.height));
+        }
+
+        return (shift_x, shift_y);
     }
 
-    pub fn render(&self, sappers: &[Sapper]) -> Surface {
-        let mut surface = Surface::new(
-            usize::from(self.size * 2).saturating_sub(1),
-            usize::from(self.size),
+    pub fn generate_random_position(&mut self) -> u32 {
+        let size_full = self.get_size_full();
+        return self.spawns_rng.gen_range(0, size_full);
+    }
+
+    pub fn render(&self, sappers: &[Sapper], width_max: usize, height_max: usize) -> Surface {
+        let row_offset_max = std::cmp::max(
+            self.topology.get_row_offset(0),
+            self.topology.get_row_offset(1),
         );
 
+        let columns_max = width_max.saturating_sub(row_offset_max).div_ceil(2);
         let mut player = None;
         let mut sapper_positions = HashSet::with_capacity(sappers.len());
 
@@ -193,82 +483,300 @@ impl Field {
             }
         }
 
-        for (i, cell) in self.cells.iter().enumerate() {
-            let cell_position = match u16::try_from(i) {
-                Ok(cell_position) => cell_position,
-                Err(_) => {
-                    // TODO: Log error
-                    break;
-                }
-            };
+        let (center_x, center_y) = player.map_or((0, 0), |s| self.to_coordinate(s.get_position()));
+        let (origin_x, columns, margin_x) = self.to_viewport(self.width, center_x, columns_max);
+        let (origin_y, rows, margin_y) = self.to_viewport(self.height, center_y, height_max);
 
-            let is_player_point = player.map_or(false, |s| s.get_position() == cell_position);
-            let mut mark = cell.get_mark(player.map_or(false, |o| o.has_marked(cell_position)));
+        let mut surface = Surface::new(
+            usize::try_from(columns * 2 - 1).unwrap_or(0) + row_offset_max,
+            usize::try_from(rows).unwrap_or(0),
+        );
 
-            if !is_player_point && sapper_positions.contains(&cell_position) {
-                mark.background = AnsiColor::Grey.into();
-            }
+        for row in 0..rows {
+            surface.add_change(Change::CursorPosition {
+                x: Position::Absolute(self.topology.get_row_offset(origin_y + row)),
+                y: Position::Absolute(usize::try_from(row).unwrap_or(0)),
+            });
+
+            for column in 0..columns {
+                let is_hint = column < margin_x
+                    || row < margin_y
+                    || column >= columns - margin_x
+                    || row >= rows - margin_y;
+
+                let (cell_position, cell) =
+                    match self.move_position(0, origin_x + column, origin_y + row) {
+                        Some(p) => match self.get_cell(p) {
+                            Some(cell) => (p, cell),
+                            None => break,
+                        },
+                        None => break,
+                    };
+
+                if column != 0 {
+                    surface.add_change(Change::Attribute(AttributeChange::Background(
+                        ColorAttribute::Default,
+                    )));
+
+                    surface.add_change(Change::Attribute(AttributeChange::Reverse(false)));
+                    surface.add_change(" ");
+                }
 
-            surface.add_change(Change::Attribute(AttributeChange::Foreground(
-                mark.foreground,
-            )));
+                let is_player_point =
+                    !is_hint && player.map_or(false, |s| s.get_position() == cell_position);
 
-            surface.add_change(Change::Attribute(AttributeChange::Background(
-                mark.background,
-            )));
+                let mut mark = cell.get_mark(player.and_then(|o| o.get_mark(cell_position)));
 
-            surface.add_change(Change::Attribute(AttributeChange::Reverse(is_player_point)));
+                if is_hint {
+                    mark.foreground = AnsiColor::Grey.into();
+                    mark.background = ColorAttribute::Default;
+                } else if !is_player_point && sapper_positions.contains(&cell_position) {
+                    mark.background = AnsiColor::Grey.into();
+                }
 
-            surface.add_change(format!("{}", mark.symbol));
+                surface.add_change(Change::Attribute(AttributeChange::Foreground(
+                    mark.foreground,
+                )));
 
-            if (cell_position + 1) % u16::from(self.size) != 0 {
                 surface.add_change(Change::Attribute(AttributeChange::Background(
-                    ColorAttribute::Default,
+                    mark.background,
                 )));
 
-                surface.add_change(Change::Attribute(AttributeChange::Reverse(false)));
-                surface.add_change(" ");
+                surface.add_change(Change::Attribute(AttributeChange::Reverse(is_player_point)));
+                surface.add_change(format!("{}", mark.symbol));
             }
         }
 
         return surface;
     }
 
-    fn is_mined(&self, position: u16) -> bool {
+    // Returns the first visible coordinate, the amount of visible cells and the margin of an axis.
+    // A wrapped field which fits the screen gets a margin showing cells from the opposite edges,
+    // while a field which doesn't fit is rendered partially around the center
+    #[allow(clippy::integer_division)] // TODO: Try to resolve
+    fn to_viewport(&self, length: u16, center: u16, count_max: usize) -> (i32, i32, i32) {
+        let length = i32::from(length);
+        let count_max = i32::try_from(count_max).unwrap_or(i32::MAX);
+        let margin = i32::from(self.is_wrapped);
+
+        if length + margin * 2 <= count_max {
+            return (-margin, length + margin * 2, margin);
+        }
+
+        let count = std::cmp::min(length, count_max);
+        let origin = i32::from(center) - count / 2;
+
+        if self.is_wrapped {
+            return (origin, count, 0);
+        } else {
+            return (origin.clamp(0, length - count), count, 0);
+        }
+    }
+
+    pub fn is_mined(&self, position: u32) -> bool {
         return self.mines.contains(&position);
     }
 
+    fn count_mines_around(&self, position: u32) -> u8 {
+        let mut mines_around = 0;
+
+        for position_near in self.around(position, false) {
+            if self.is_mined(position_near) {
+                mines_around += 1;
+            }
+        }
+
+        return mines_around;
+    }
+
     pub fn is_cleaned(&self) -> bool {
         return self.get_cells_undiscovered_count() == 0;
     }
 
-    pub const fn get_size(&self) -> u8 {
-        return self.size;
+    pub fn get_topology(&self) -> &dyn Topology {
+        return self.topology.as_ref();
     }
 
-    pub fn get_size_full(&self) -> u16 {
-        let size_u16 = u16::from(self.size);
-        return size_u16 * size_u16;
+    pub fn set_topology(&mut self, topology: Box<dyn Topology>) {
+        self.topology = topology;
     }
 
-    pub const fn get_cells(&self) -> &Vec<Cell> {
-        return &self.cells;
+    pub const fn is_wrapped(&self) -> bool {
+        return self.is_wrapped;
     }
 
-    pub fn get_cell(&self, position: u16) -> Option<&Cell> {
-        return self.cells.get(usize::from(position));
+    pub fn set_wrapped(&mut self, is_wrapped: bool) {
+        self.is_wrapped = is_wrapped;
     }
 
-    pub fn get_cell_mut(&mut self, position: u16) -> Option<&mut Cell> {
-        return self.cells.get_mut(usize::from(position));
+    pub const fn get_width(&self) -> u16 {
+        return self.width;
+    }
+
+    pub const fn get_height(&self) -> u16 {
+        return self.height;
+    }
+
+    pub fn get_size_full(&self) -> u32 {
+        return u32::from(self.width) * u32::from(self.height);
+    }
+
+    // Iterates over the allocated cells only, the rest of them are untouched anyway
+    pub fn iter_cells(&self) -> impl Iterator<Item = (u32, &Cell)> {
+        return self.chunks.iter().flat_map(move |(chunk, cells)| {
+            return cells
+                .iter()
+                .enumerate()
+                .filter_map(move |(i, cell)| self.to_position(*chunk, i).map(|p| (p, cell)));
+        });
+    }
+
+    pub fn get_cell(&self, position: u32) -> Option<&Cell> {
+        if position >= self.get_size_full() {
+            return None;
+        }
+
+        let (chunk, i) = self.to_chunk(position);
+
+        return match self.chunks.get(&chunk) {
+            Some(cells) => cells.get(i),
+            None => Some(&CELL_UNTOUCHED),
+        };
+    }
+
+    pub fn get_cell_mut(&mut self, position: u32) -> Option<&mut Cell> {
+        if position >= self.get_size_full() {
+            return None;
+        }
+
+        self.touch(position);
+        let (chunk, i) = self.to_chunk(position);
+        return self.chunks.get_mut(&chunk).and_then(|c| c.get_mut(i));
+    }
+
+    // Allocates the chunk of a position, rolling its mines if the field is chunked
+    fn touch(&mut self, position: u32) {
+        if position >= self.get_size_full() {
+            return;
+        }
+
+        let (chunk, _) = self.to_chunk(position);
+
+        if self.chunks.contains_key(&chunk) {
+            return;
+        }
+
+        self.chunks.insert(
+            chunk,
+            std::iter::repeat_with(Cell::new).take(CHUNK_AREA).collect(),
+        );
+
+        if self.is_generated {
+            if let Generator::Chunked = self.generator {
+                self.roll_chunk_mines(chunk);
+            }
+        }
+    }
+
+    #[allow(clippy::integer_division)] // TODO: Try to resolve
+    fn to_chunk(&self, position: u32) -> (u32, usize) {
+        let (x, y) = self.to_coordinate(position);
+        let chunk_x = u32::from(x / CHUNK_SIZE);
+        let chunk_y = u32::from(y / CHUNK_SIZE);
+        let i = usize::from(y % CHUNK_SIZE) * usize::from(CHUNK_SIZE) + usize::from(x % CHUNK_SIZE);
+        return (chunk_y * self.get_chunks_per_row() + chunk_x, i);
+    }
+
+    #[allow(clippy::integer_division)] // TODO: Try to resolve
+    fn to_position(&self, chunk: u32, i: usize) -> Option<u32> {
+        let chunks_per_row = self.get_chunks_per_row();
+        let size = u32::from(CHUNK_SIZE);
+        let i = u32::try_from(i).ok()?;
+        let x = (chunk % chunks_per_row) * size + i % size;
+        let y = (chunk / chunks_per_row) * size + i / size;
+
+        if x < u32::from(self.width) && y < u32::from(self.height) {
+            return Some(y * u32::from(self.width) + x);
+        } else {
+            return None;
+        }
+    }
+
+    fn get_chunks_per_row(&self) -> u32 {
+        return u32::from(self.width).div_ceil(u32::from(CHUNK_SIZE));
     }
 
     pub fn get_mines_count(&self) -> usize {
-        return self.mines.len();
+        if self.is_generated {
+            return self.mines.len();
+        } else if let MinesAmount::Count(count) = self.mines_amount {
+            return std::cmp::min(
+                utils::u32_to_usize_saturating(count),
+                self.get_cells_count(),
+            );
+        } else {
+            return 0;
+        }
+    }
+
+    pub const fn is_generated(&self) -> bool {
+        return self.is_generated;
+    }
+
+    pub const fn is_mines_count_known(&self) -> bool {
+        if let Generator::Chunked = self.generator {
+            return false;
+        }
+
+        return self.is_generated || matches!(self.mines_amount, MinesAmount::Count(_));
+    }
+
+    pub const fn get_seed(&self) -> u32 {
+        return self.seed;
+    }
+
+    pub fn set_seed(&mut self, seed: u32) {
+        self.seed = seed;
+        self.spawns_rng = StdRng::seed_from_u64(u64::from(seed) ^ SPAWNS_SEED_SALT);
+    }
+
+    pub fn set_opening(&mut self, opening: Opening) {
+        self.opening = opening;
+    }
+
+    pub fn set_generator(&mut self, generator: Generator) {
+        self.generator = generator;
+        self.convert_mines_count();
+    }
+
+    pub const fn get_mines_amount(&self) -> MinesAmount {
+        return self.mines_amount;
+    }
+
+    pub fn set_mines_amount(&mut self, mines_amount: MinesAmount) {
+        self.mines_amount = mines_amount;
+        self.convert_mines_count();
+    }
+
+    // Chunks roll their mines by density alone, so a count is turned into the same share of cells
+    // rather than leaving the field without mines
+    fn convert_mines_count(&mut self) {
+        if let (Generator::Chunked, MinesAmount::Count(count)) = (self.generator, self.mines_amount)
+        {
+            let density = f64::from(count) / f64::from(self.get_size_full());
+            self.mines_amount = MinesAmount::Density(density.min(1.0));
+        }
+    }
+
+    // Places a predefined layout instead of generating one
+    pub fn set_mines(&mut self, mines: HashSet<u32>) {
+        self.mines_amount = MinesAmount::Count(u32::try_from(mines.len()).unwrap_or(u32::MAX));
+        self.mines = mines;
+        self.is_generated = true;
     }
 
     pub fn get_cells_count(&self) -> usize {
-        return self.cells.len();
+        return utils::u32_to_usize_saturating(self.get_size_full());
     }
 
     pub const fn get_cells_discovered_count(&self) -> usize {
@@ -280,8 +788,249 @@ impl Field {
     }
 }
 
+// Takes the steps off the ones left, or all of them when there aren't enough
+fn spend_steps(steps_left: &mut usize, steps: usize) -> bool {
+    let is_enough = *steps_left >= steps;
+    *steps_left = steps_left.saturating_sub(steps);
+    return is_enough;
+}
+
 pub enum DiscoveryResult {
-    Success,
+    Success(Vec<u32>),
     Failure,
     AlreadyDiscovered,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_mines_no_guess() {
+        for seed in 0..8 {
+            let mut field = Field::new(16, 16, MinesAmount::Count(40), seed);
+            field.set_generator(Generator::NoGuess);
+            field.generate_mines(&[0], &mut EventManager::new());
+
+            let mut steps_left = NO_GUESS_STEPS_MAX;
+            assert_eq!(field.get_mines_count(), 40, "Mines count, seed {}", seed);
+            assert!(
+                field.is_solvable(&[0], &mut steps_left),
+                "Solvable, seed {}",
+                seed
+            );
+        }
+
+        // Even a field which can't be made solvable falls back to the same mines for a seed
+        let generate = || {
+            let mut field = Field::new(8, 8, MinesAmount::Count(40), 3);
+            field.set_generator(Generator::NoGuess);
+            field.generate_mines(&[0], &mut EventManager::new());
+            return field.mines;
+        };
+
+        assert!(generate() == generate(), "Same fallback");
+    }
+
+    #[test]
+    fn test_generate_mines_no_guess_large() {
+        // Too big to be made solvable with the steps given, yet it's done in bounded time
+        let mut field = Field::new(400, 400, MinesAmount::Density(0.2), 1);
+        field.set_generator(Generator::NoGuess);
+        field.generate_mines(&[0], &mut EventManager::new());
+        assert!(field.is_generated, "Fallen back");
+
+        let mut steps_left = field.get_cells_count();
+        assert!(!field.is_solvable(&[0], &mut steps_left), "Out of steps");
+        assert_eq!(steps_left, 0, "Steps spent");
+    }
+
+    #[test]
+    fn test_generate_mines_count() {
+        let mut field = Field::new(4, 4, MinesAmount::Count(100), 0);
+        field.generate_mines(&[5], &mut EventManager::new());
+
+        assert_eq!(field.get_mines_count(), 7, "Clamped to free cells");
+
+        for position in field.around(5, true) {
+            assert!(!field.is_mined(position), "Opening is safe");
+        }
+    }
+
+    #[test]
+    fn test_generate_mines_opening() {
+        let mut field = Field::new(8, 8, MinesAmount::Density(1.0), 0);
+        field.set_opening(Opening::Unprotected);
+        field.generate_mines(&[0], &mut EventManager::new());
+        assert_eq!(field.get_mines_count(), 64, "Unprotected");
+
+        let mut field = Field::new(8, 8, MinesAmount::Density(1.0), 0);
+        field.set_opening(Opening::SafeCell);
+        field.generate_mines(&[0, 63], &mut EventManager::new());
+        assert_eq!(field.get_mines_count(), 62, "Safe cells");
+
+        let mut field = Field::new(8, 8, MinesAmount::Density(1.0), 0);
+        field.set_opening(Opening::Zero);
+        field.generate_mines(&[27], &mut EventManager::new());
+        assert_eq!(field.get_mines_count(), 64 - 25, "Zero");
+        assert_eq!(field.count_mines_around(27), 0, "Zero opening");
+
+        for position in field.around(27, false) {
+            assert_eq!(field.count_mines_around(position), 0, "Zero around opening");
+        }
+    }
+
+    #[test]
+    fn test_wrapped() {
+        let mut field = Field::new(4, 3, MinesAmount::Count(0), 0);
+        field.set_wrapped(true);
+
+        let mut around = field.around(0, false);
+        around.sort_unstable();
+        assert_eq!(around, vec![1, 3, 4, 5, 7, 8, 9, 11], "Around the corner");
+
+        assert_eq!(
+            field.move_position(0, -1, -1),
+            Some(11),
+            "Move through the corner"
+        );
+        assert_eq!(field.to_shift(0, 3), (-1, 0), "Shift through the edge");
+        assert_eq!(field.to_distance(0, 11), 2, "Distance through the corner");
+
+        let mut field = Field::new(2, 1, MinesAmount::Count(0), 0);
+        field.set_wrapped(true);
+        assert_eq!(field.around(0, false), vec![1], "Small field");
+    }
+
+    #[test]
+    fn test_hex() {
+        let mut field = Field::new(4, 4, MinesAmount::Count(0), 0);
+        field.set_topology(Box::new(crate::topology::HexTopology {}));
+
+        let mut around = field.around(5, false);
+        around.sort_unstable();
+        assert_eq!(around, vec![1, 2, 4, 6, 9, 10], "Around on an odd row");
+
+        let mut around = field.around(9, false);
+        around.sort_unstable();
+        assert_eq!(around, vec![4, 5, 8, 10, 12, 13], "Around on an even row");
+
+        assert_eq!(field.step(5, Direction::Up), Some(1), "Zigzag up");
+        assert_eq!(field.step(1, Direction::Down), Some(5), "Zigzag down");
+        assert_eq!(field.step(5, Direction::UpRight), Some(2), "Diagonal");
+    }
+
+    #[test]
+    fn test_discover_large_empty() {
+        let mut field = Field::new(300, 300, MinesAmount::Count(0), 0);
+        let mut events = EventManager::new();
+
+        if let DiscoveryResult::Success(revealed) = field.discover(0, &mut events) {
+            let unique = revealed.iter().collect::<HashSet<_>>();
+            assert_eq!(
+                revealed.len(),
+                field.get_cells_count(),
+                "Revealed in one batch"
+            );
+            assert_eq!(unique.len(), revealed.len(), "Revealed once");
+        } else {
+            panic!("Failed to discover");
+        }
+
+        assert!(field.is_cleaned(), "Cleaned");
+        assert_eq!(events.pull().len(), field.get_cells_count() + 1, "Events");
+    }
+
+    #[test]
+    fn test_discover_large_sparse() {
+        let mut field = Field::new(300, 200, MinesAmount::Count(20), 7);
+        let mut events = EventManager::new();
+        let position = field.get_size_full() / 2;
+
+        let revealed = match field.discover(position, &mut events) {
+            DiscoveryResult::Success(revealed) => revealed,
+            _ => panic!("Failed to discover"),
+        };
+
+        assert_eq!(
+            revealed.first(),
+            Some(&position),
+            "Starts from the discovered cell"
+        );
+        assert_eq!(
+            revealed.len(),
+            field.get_cells_discovered_count(),
+            "Counted"
+        );
+
+        for position in &revealed {
+            assert!(!field.is_mined(*position), "Mines stay hidden");
+        }
+
+        for (position, cell) in field.iter_cells() {
+            if cell.mines_around == Some(0) {
+                for position_near in field.around(position, false) {
+                    let is_discovered = field.get_cell(position_near).unwrap().is_discovered();
+                    assert!(is_discovered, "Zero cell neighbors are revealed");
+                }
+            }
+        }
+
+        assert!(
+            matches!(
+                field.discover(position, &mut events),
+                DiscoveryResult::AlreadyDiscovered
+            ),
+            "Already discovered"
+        );
+    }
+
+    #[test]
+    fn test_chunked() {
+        let create = || {
+            let mut field = Field::new(u16::MAX, u16::MAX, MinesAmount::Density(0.15), 3);
+            field.set_wrapped(true);
+            field.set_generator(Generator::Chunked);
+            return field;
+        };
+
+        let position = 1_000_000;
+        let mut field_1 = create();
+        let mut field_2 = create();
+        field_2.touch(position + 100);
+        field_2.touch(0);
+
+        let revealed_1 = match field_1.discover(position, &mut EventManager::new()) {
+            DiscoveryResult::Success(revealed) => revealed,
+            _ => panic!("Failed to discover"),
+        };
+
+        let revealed_2 = match field_2.discover(position, &mut EventManager::new()) {
+            DiscoveryResult::Success(revealed) => revealed,
+            _ => panic!("Failed to discover"),
+        };
+
+        assert_eq!(revealed_1, revealed_2, "Regardless of access order");
+        assert!(field_1.iter_cells().count() < 100_000, "Lazy allocation");
+        assert_eq!(field_1.count_mines_around(position), 0, "Safe opening");
+
+        for position_near in field_1.around(0, true) {
+            field_1.touch(position_near);
+            field_2.touch(position_near);
+
+            assert_eq!(
+                field_1.is_mined(position_near),
+                field_2.is_mined(position_near),
+                "Deterministic mines",
+            );
+        }
+
+        let mut field = Field::new(1000, 1000, MinesAmount::Count(150_000), 0);
+        field.set_generator(Generator::Chunked);
+
+        assert!(
+            matches!(field.get_mines_amount(), MinesAmount::Density(d) if (d - 0.15).abs() < 0.001),
+            "Count as density"
+        );
+    }
+}
@@ -1,7 +1,13 @@
 use crate::event::Event;
 use crate::event::EventManager;
 use crate::field::Field;
+use crate::sapper::Rival;
 use crate::sapper::Sapper;
+use crate::scoring::Scoring;
+use crate::task_board::TaskBoard;
+use crate::utils;
+use rand::prelude::*;
+use std::time::Instant;
 use termwiz::cell::AttributeChange;
 use termwiz::color::AnsiColor;
 use termwiz::color::ColorAttribute;
@@ -9,30 +15,137 @@ use termwiz::input::InputEvent;
 use termwiz::surface::Change;
 use termwiz::surface::Surface;
 
-const STATISTICS_WIDTH: usize = 16;
+const BOTS_SEED_SALT: u64 = 0x424F_5453; // Keeps slips of bots independent from the field
+const STATISTICS_WIDTH: usize = 21;
+const RESULTS_WIDTH: usize = 32;
+const STATE_IN_PROGRESS_ID: u8 = 0;
+const STATE_WON_ID: u8 = 1;
+const STATE_LOST_ID: u8 = 2;
+const STATE_DRAW_ID: u8 = 3;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GameState {
+    InProgress,
+    Won(u8),
+    Lost,
+    Draw,
+}
+
+impl GameState {
+    pub const fn to_id(self) -> u8 {
+        return match self {
+            Self::InProgress => STATE_IN_PROGRESS_ID,
+            Self::Won(_) => STATE_WON_ID,
+            Self::Lost => STATE_LOST_ID,
+            Self::Draw => STATE_DRAW_ID,
+        };
+    }
+
+    pub const fn from_id(id: u8, winner: u8) -> Self {
+        return match id {
+            STATE_WON_ID => Self::Won(winner),
+            STATE_LOST_ID => Self::Lost,
+            STATE_DRAW_ID => Self::Draw,
+            _ => Self::InProgress,
+        };
+    }
+
+    pub const fn get_winner(self) -> u8 {
+        return match self {
+            Self::Won(winner) => winner,
+            _ => 0,
+        };
+    }
+}
 
 pub struct Game {
     pub field: Field,
     pub sappers: Vec<Sapper>,
     pub events: EventManager,
+    pub state: GameState,
+    lives: u8,
+    scoring: Scoring,
+    started: Option<Instant>,
+    time: u32,
+    time_limit: u32,
+    task_board: TaskBoard,
+    bots_rng: StdRng,
 }
 
 impl Game {
     pub fn new(field: Field, sappers: Vec<Sapper>) -> Self {
+        let bots_rng = StdRng::seed_from_u64(u64::from(field.get_seed()) ^ BOTS_SEED_SALT);
+
         return Self {
             field,
             sappers,
             events: EventManager::new(),
+            state: GameState::InProgress,
+            lives: 1,
+            scoring: Scoring::new(),
+            started: None,
+            time: 0,
+            time_limit: 0,
+            task_board: TaskBoard::new(),
+            bots_rng,
         };
     }
 
+    // The lives of every sapper who joins the game, one means the classic instant death
+    pub fn set_lives(&mut self, lives: u8) {
+        self.lives = lives;
+
+        for sapper in &mut self.sappers {
+            sapper.lives = lives;
+        }
+    }
+
+    pub const fn get_lives(&self) -> u8 {
+        return self.lives;
+    }
+
+    pub fn set_scoring(&mut self, scoring: Scoring) {
+        self.scoring = scoring;
+    }
+
+    pub const fn get_scoring(&self) -> Scoring {
+        return self.scoring;
+    }
+
     pub fn update(&mut self, input: Option<&InputEvent>) -> Vec<Event> {
         let mut explode_mines = !self.sappers.is_empty();
         let mut local_events = Vec::new();
 
-        if !self.field.is_cleaned() {
+        if self.state == GameState::InProgress && !self.field.is_cleaned() {
+            let rivals = self
+                .sappers
+                .iter()
+                .filter(|s| s.is_alive())
+                .map(|s| Rival {
+                    id: s.get_id(),
+                    position: s.get_position(),
+                    target: s.get_target(),
+                })
+                .collect::<Vec<_>>();
+
+            // Claims of the dead and of the cells which are known already are no use anymore
+            let field = &self.field;
+
+            self.task_board.retain(|position, id| {
+                return rivals.iter().any(|r| r.id == id)
+                    && field
+                        .get_cell(position)
+                        .map_or(false, |c| !c.is_discovered() && !c.is_exploded);
+            });
+
             for sapper in &mut self.sappers {
-                sapper.update(&mut self.field, input);
+                sapper.update(
+                    &self.field,
+                    input,
+                    &rivals,
+                    &mut self.task_board,
+                    &mut self.bots_rng,
+                );
 
                 if explode_mines && sapper.is_alive() {
                     explode_mines = false;
@@ -54,33 +167,127 @@ impl Game {
         return local_events;
     }
 
-    pub fn render(&self) -> Surface {
+    // The game is over once the field is cleaned, every sapper is dead or the time is over. Only
+    // survivors compete for the victory then, and the best score wins unless it's shared
+    pub fn resolve_state(&self) -> GameState {
+        let is_time_over = self.time_limit != 0 && self.time >= self.time_limit;
+
+        let survivors = self
+            .get_sappers_sorted_by_score()
+            .into_iter()
+            .filter(|s| s.is_alive())
+            .collect::<Vec<_>>();
+
+        if self.sappers.is_empty()
+            || (!survivors.is_empty() && !self.field.is_cleaned() && !is_time_over)
+        {
+            return GameState::InProgress;
+        }
+
+        match (survivors.first(), survivors.get(1)) {
+            (Some(first), Some(second)) if first.get_score() == second.get_score() => {
+                return GameState::Draw;
+            }
+            (Some(first), _) => {
+                return GameState::Won(first.get_id());
+            }
+            (None, _) => {
+                return GameState::Lost;
+            }
+        }
+    }
+
+    // The clock is started by the first discovery on the server, which then keeps the clients in
+    // sync with the time it measures
+    pub fn start_clock(&mut self) {
+        if self.started.is_none() {
+            self.started = Some(Instant::now());
+        }
+    }
+
+    pub fn get_clock(&self) -> u32 {
+        return self.started.map_or(0, |started| {
+            u32::try_from(started.elapsed().as_secs()).unwrap_or(u32::MAX)
+        });
+    }
+
+    pub fn set_time(&mut self, time: u32) {
+        self.time = time;
+    }
+
+    // Seconds since the first discovery as the server has reported it
+    pub const fn get_time(&self) -> u32 {
+        return self.time;
+    }
+
+    // Zero means there is no limit
+    pub fn set_time_limit(&mut self, time_limit: u32) {
+        self.time_limit = time_limit;
+    }
+
+    pub const fn get_time_limit(&self) -> u32 {
+        return self.time_limit;
+    }
+
+    pub fn render(&self, width: usize, height: usize) -> Surface {
         let statistics = self.render_statistics();
-        let field = self.field.render(&self.sappers);
         let (statistics_width, statistics_height) = statistics.dimensions();
+        let field = self.field.render(
+            &self.sappers,
+            width.saturating_sub(statistics_width),
+            height,
+        );
         let (field_width, field_height) = field.dimensions();
 
+        let results = if self.state == GameState::InProgress {
+            None
+        } else {
+            Some(self.render_results())
+        };
+
+        let (results_width, results_height) = results.as_ref().map_or((0, 0), Surface::dimensions);
+
         let mut surface = Surface::new(
-            statistics_width + field_width,
-            std::cmp::max(statistics_height, field_height),
+            statistics_width + std::cmp::max(field_width, results_width),
+            std::cmp::max(
+                statistics_height,
+                std::cmp::max(field_height, results_height),
+            ),
         );
 
         surface.draw_from_screen(&statistics, 0, 0);
         surface.draw_from_screen(&field, statistics_width, 0);
 
+        // The results cover the field, which is still visible around them
+        if let Some(results) = results {
+            surface.draw_from_screen(&results, statistics_width, 0);
+        }
+
         return surface;
     }
 
     pub fn render_statistics(&self) -> Surface {
-        let mut surface = Surface::new(STATISTICS_WIDTH, self.sappers.len() + 5);
-        let marks = self.get_player().map_or(0, Sapper::get_marks_count);
+        let mut surface = Surface::new(STATISTICS_WIDTH, self.sappers.len() + 7);
+        let marks = self.get_player().map_or(0, Sapper::get_flags_count);
+
+        // Big fields have more cells than four digits can tell
+        let short = |count: usize| utils::format_short(u32::try_from(count).unwrap_or(u32::MAX));
+
+        let time_limit = if self.time_limit == 0 {
+            "--:--".to_owned()
+        } else {
+            utils::format_time(self.time_limit)
+        };
 
         surface.add_change(format!(
-            "     #GOT #REM  #CLS {:04} {:04}  #MNS {:04} {:04}                  #POS #SPR #SCR  ",
-            self.field.get_cells_discovered_count(),
-            self.field.get_cells_undiscovered_count(),
-            marks,
-            self.field.get_mines_count().saturating_sub(marks),
+            "     #GOT #REM       #CLS {} {}       #MNS {} {}       #SED {:010}      #TIM {} {}                          #POS #SPR #SCR #LIV  ",
+            short(self.field.get_cells_discovered_count()),
+            short(self.field.get_cells_undiscovered_count()),
+            short(marks),
+            short(self.field.get_mines_count().saturating_sub(marks)),
+            self.field.get_seed(),
+            utils::format_time(self.time),
+            time_limit,
         ));
 
         for (i, sapper) in self.get_sappers_sorted_by_score().iter().enumerate() {
@@ -101,10 +308,64 @@ impl Game {
             }
 
             surface.add_change(format!(
-                "{:04}  {} {:04}",
+                "{:04}  {} {} {:04}",
                 i + 1,
                 sapper.get_name(),
-                sapper.get_score(),
+                utils::format_short(sapper.get_score()),
+                sapper.get_lives(),
+            ));
+
+            surface.add_change(Change::Attribute(AttributeChange::Foreground(
+                ColorAttribute::Default,
+            )));
+            surface.add_change(Change::Attribute(AttributeChange::Reverse(false)));
+            surface.add_change("  ");
+        }
+
+        return surface;
+    }
+
+    pub fn render_results(&self) -> Surface {
+        let mut surface = Surface::new(RESULTS_WIDTH, self.sappers.len() + 3);
+        let player_id = self.get_player().map(Sapper::get_id);
+
+        let (title, color) = match self.state {
+            GameState::Won(winner) if Some(winner) == player_id => ("VICTORY", AnsiColor::Green),
+            GameState::Draw => ("DRAW", AnsiColor::Olive),
+            _ => ("DEFEAT", AnsiColor::Red),
+        };
+
+        surface.add_change(Change::Attribute(AttributeChange::Foreground(color.into())));
+        surface.add_change(format!("{:<1$}", title, RESULTS_WIDTH));
+        surface.add_change(Change::Attribute(AttributeChange::Foreground(
+            ColorAttribute::Default,
+        )));
+        surface.add_change(format!("{:<1$}", "", RESULTS_WIDTH));
+        surface.add_change("#POS #SPR #SCR #TIME #CLS #ERR  ");
+
+        for (i, sapper) in self.get_sappers_sorted_by_score().iter().enumerate() {
+            if sapper.is_player() {
+                surface.add_change(Change::Attribute(AttributeChange::Reverse(true)));
+            }
+
+            if self.state == GameState::Won(sapper.get_id()) {
+                surface.add_change(Change::Attribute(AttributeChange::Foreground(
+                    AnsiColor::Green.into(),
+                )));
+            } else if !sapper.is_alive() {
+                surface.add_change(Change::Attribute(AttributeChange::Foreground(
+                    AnsiColor::Red.into(),
+                )));
+            }
+
+            surface.add_change(format!(
+                "{:04}  {} {} {} {} {:04}",
+                i + 1,
+                sapper.get_name(),
+                utils::format_short(sapper.get_score()),
+                utils::format_time(sapper.time),
+                utils::format_short(sapper.cells),
+                sapper.mistakes,
             ));
 
             surface.add_change(Change::Attribute(AttributeChange::Foreground(
@@ -154,3 +415,60 @@ impl Game {
         return None;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::MinesAmount;
+    use crate::sapper::SapperBehavior;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_resolve_state() {
+        let mut field = Field::new(2, 1, MinesAmount::Count(0), 0);
+        field.set_mines(HashSet::from([1]));
+
+        let sappers = vec![
+            Sapper::new(0, SapperBehavior::Bot, 0, 0.0),
+            Sapper::new(1, SapperBehavior::Bot, 0, 0.0),
+        ];
+
+        let mut game = Game::new(field, sappers);
+        assert_eq!(game.resolve_state(), GameState::InProgress, "Started");
+
+        game.field.discover(0, &mut EventManager::new());
+        assert_eq!(game.resolve_state(), GameState::Draw, "Shared score");
+
+        if let Some(sapper) = game.get_sapper_mut(1) {
+            sapper.score = 1;
+        }
+
+        assert_eq!(game.resolve_state(), GameState::Won(1), "Best score");
+
+        if let Some(sapper) = game.get_sapper_mut(1) {
+            sapper.is_alive = false;
+        }
+
+        assert_eq!(game.resolve_state(), GameState::Won(0), "Survivor");
+
+        if let Some(sapper) = game.get_sapper_mut(0) {
+            sapper.is_alive = false;
+        }
+
+        assert_eq!(game.resolve_state(), GameState::Lost, "Everyone died");
+    }
+
+    #[test]
+    fn test_render_statistics() {
+        let field = Field::new(u16::MAX - 1, u16::MAX - 1, MinesAmount::Density(0.2), 0);
+        let game = Game::new(field, vec![Sapper::new(0, SapperBehavior::Player, 0, 0.0)]);
+        let surface = game.render_statistics();
+        let lines = surface.screen_lines();
+
+        assert_eq!(
+            lines.get(1).map(|l| l.as_str()).as_deref(),
+            Some("#CLS 0000 004B       "),
+            "Cells"
+        );
+    }
+}
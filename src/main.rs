@@ -37,12 +37,21 @@
 )]
 
 mod application;
+mod board;
 mod cell;
 mod event;
+mod export;
 mod field;
 mod game;
 mod net;
+mod profile;
+mod route;
 mod sapper;
+mod scoring;
+mod skill;
+mod solver;
+mod task_board;
+mod topology;
 mod ui;
 mod utils;
 
@@ -3,6 +3,7 @@ use crate::event::EventData;
 use crate::event::EventManager;
 use crate::event::EVENT_SIZE;
 use crate::field::Field;
+use crate::field::MinesAmount;
 use crate::game::Game;
 use crate::net::LocalMessage;
 use crate::net::Message;
@@ -10,6 +11,7 @@ use crate::net::NetHandler;
 use crate::net::NO_SENDER;
 use crate::sapper::Sapper;
 use crate::sapper::SapperBehavior;
+use crate::topology;
 use async_std::net::TcpStream;
 use async_std::prelude::*;
 use futures::executor::block_on;
@@ -30,6 +32,7 @@ pub struct Client {
     sender: Sender<Message>,
     receiver: Receiver<Message>,
     thread: Option<JoinHandle<()>>,
+    is_question_blocking: bool,
     pub error: Option<String>,
 }
 
@@ -48,14 +51,19 @@ impl Client {
             .map_err(|e| format!("{}", e))?;
 
         return Ok(Self {
-            game: Game::new(Field::new(0, 0.0), Vec::new()),
+            game: Game::new(Field::new(0, 0, MinesAmount::Count(0), 0), Vec::new()),
             sender: client_sender,
             receiver: client_receiver,
             thread: Some(thread),
+            is_question_blocking: true,
             error: None,
         });
     }
 
+    pub fn set_question_blocking(&mut self, is_question_blocking: bool) {
+        self.is_question_blocking = is_question_blocking;
+    }
+
     #[allow(warnings)] // TODO: Resolve
     async fn run(stream: TcpStream, sender: Sender<Message>, receiver: Receiver<Message>) {
         let mut stream_reading = stream;
@@ -210,13 +218,14 @@ impl NetHandler for Client {
     fn on_sapper_connect_response(&mut self, id: u8) -> bool {
         if let Some(sapper) = self.game.get_sapper_mut(id) {
             sapper.behavior = SapperBehavior::Player;
+            sapper.set_question_blocking(self.is_question_blocking);
             return true;
         } else {
             return false;
         }
     }
 
-    fn on_sapper_spawn(&mut self, id: u8, position: u16) -> bool {
+    fn on_sapper_spawn(&mut self, id: u8, position: u32) -> bool {
         self.game
             .sappers
             .push(Sapper::new(id, SapperBehavior::Remote, position, 0.0));
@@ -224,12 +233,32 @@ impl NetHandler for Client {
         return true;
     }
 
-    fn on_field_create(&mut self, size: u8) -> bool {
-        self.game.field = Field::new(size, 0.0);
+    fn on_field_create(&mut self, width: u16, height: u16, topology: u8, is_wrapped: bool) -> bool {
+        self.game.field = Field::new(width, height, MinesAmount::Count(0), 0);
+        self.game.field.set_topology(topology::from_id(topology));
+        self.game.field.set_wrapped(is_wrapped);
+        return true;
+    }
+
+    fn on_field_mines(&mut self, count: u32) -> bool {
+        if self.game.field.get_cells_count() == 0 {
+            return false;
+        }
+
+        self.game.field.set_mines_amount(MinesAmount::Count(count));
+        return true;
+    }
+
+    fn on_field_seed(&mut self, seed: u32) -> bool {
+        if self.game.field.get_cells_count() == 0 {
+            return false;
+        }
+
+        self.game.field.set_seed(seed);
         return true;
     }
 
-    fn on_cell_discover(&mut self, position: u16, mines_around: u8) -> bool {
+    fn on_cell_discover(&mut self, position: u32, mines_around: u8) -> bool {
         if let Some(cell) = self.game.field.get_cell_mut(position) {
             cell.mines_around = Some(mines_around);
             return true;
@@ -238,9 +267,10 @@ impl NetHandler for Client {
         return false;
     }
 
-    fn on_cell_explode(&mut self, position: u16) -> bool {
+    fn on_cell_explode(&mut self, position: u32) -> bool {
         if let Some(cell) = self.game.field.get_cell_mut(position) {
             cell.is_exploded = true;
+            cell.is_hit = true;
             return true;
         }
 
@@ -1,10 +1,12 @@
 pub mod client;
 pub mod server;
 
+use crate::cell::Mark;
 use crate::event::Event;
 use crate::event::EventData;
 use crate::event::EventManager;
 use crate::game::Game;
+use crate::game::GameState;
 use crate::net::server::ServerClient;
 use std::net::SocketAddr;
 use termwiz::input::InputEvent;
@@ -47,8 +49,12 @@ pub trait NetHandler {
 
         // TODO: Optimize with swap
         self.get_events_mut().fire_all(&mut suspended);
+
+        self.after_update();
     }
 
+    fn after_update(&mut self) {}
+
     // TODO: Async, also allow sent multiple events in parallel
     fn send(&mut self, event: Event);
 
@@ -75,14 +81,44 @@ pub trait NetHandler {
             EventData::SapperDiscover { id, position } => {
                 was_processed = self.on_sapper_discover(id, position);
             }
+            EventData::SapperMark { id, position, mark } => {
+                was_processed = self.on_sapper_mark(id, position, mark);
+            }
+            EventData::SapperChord { id, position } => {
+                was_processed = self.on_sapper_chord(id, position);
+            }
             EventData::SapperScore { id, score } => {
                 was_processed = self.on_sapper_score(id, score);
             }
+            EventData::SapperLives { id, lives } => {
+                was_processed = self.on_sapper_lives(id, lives);
+            }
             EventData::SapperDie { id } => {
                 was_processed = self.on_sapper_die(id);
             }
-            EventData::FieldCreate { size } => {
-                was_processed = self.on_field_create(size);
+            EventData::SapperResult {
+                id,
+                cells,
+                mistakes,
+            } => {
+                was_processed = self.on_sapper_result(id, cells, mistakes);
+            }
+            EventData::SapperTime { id, time } => {
+                was_processed = self.on_sapper_time(id, time);
+            }
+            EventData::FieldCreate {
+                width,
+                height,
+                topology,
+                is_wrapped,
+            } => {
+                was_processed = self.on_field_create(width, height, topology, is_wrapped);
+            }
+            EventData::FieldMines { count } => {
+                was_processed = self.on_field_mines(count);
+            }
+            EventData::FieldSeed { seed } => {
+                was_processed = self.on_field_seed(seed);
             }
             EventData::CellDiscover {
                 position,
@@ -93,6 +129,12 @@ pub trait NetHandler {
             EventData::CellExplode { position } => {
                 was_processed = self.on_cell_explode(position);
             }
+            EventData::GameTime { time, limit } => {
+                was_processed = self.on_game_time(time, limit);
+            }
+            EventData::GameOver { state, winner } => {
+                was_processed = self.on_game_over(state, winner);
+            }
         }
 
         if was_processed {
@@ -114,11 +156,11 @@ pub trait NetHandler {
         return true;
     }
 
-    fn on_sapper_spawn(&mut self, _id: u8, _position: u16) -> bool {
+    fn on_sapper_spawn(&mut self, _id: u8, _position: u32) -> bool {
         return true;
     }
 
-    fn on_sapper_move(&mut self, id: u8, position: u16) -> bool {
+    fn on_sapper_move(&mut self, id: u8, position: u32) -> bool {
         if let Some(sapper) = self.get_game_mut().get_sapper_mut(id) {
             sapper.position = position;
             return true;
@@ -127,11 +169,24 @@ pub trait NetHandler {
         }
     }
 
-    fn on_sapper_discover(&mut self, _id: u8, _position: u16) -> bool {
+    fn on_sapper_discover(&mut self, _id: u8, _position: u32) -> bool {
+        return true;
+    }
+
+    fn on_sapper_mark(&mut self, id: u8, position: u32, mark: u8) -> bool {
+        if let Some(sapper) = self.get_game_mut().get_sapper_mut(id) {
+            sapper.set_mark(position, Mark::from_id(mark));
+            return true;
+        } else {
+            return false;
+        }
+    }
+
+    fn on_sapper_chord(&mut self, _id: u8, _position: u32) -> bool {
         return true;
     }
 
-    fn on_sapper_score(&mut self, id: u8, score: u16) -> bool {
+    fn on_sapper_score(&mut self, id: u8, score: u32) -> bool {
         if let Some(sapper) = self.get_game_mut().get_sapper_mut(id) {
             sapper.score = score;
             return true;
@@ -140,24 +195,79 @@ pub trait NetHandler {
         }
     }
 
+    fn on_sapper_lives(&mut self, id: u8, lives: u8) -> bool {
+        if let Some(sapper) = self.get_game_mut().get_sapper_mut(id) {
+            sapper.lives = lives;
+            return true;
+        } else {
+            return false;
+        }
+    }
+
     fn on_sapper_die(&mut self, id: u8) -> bool {
         if let Some(sapper) = self.get_game_mut().get_sapper_mut(id) {
             sapper.is_alive = false;
+            sapper.lives = 0;
+            return true;
+        } else {
+            return false;
+        }
+    }
+
+    fn on_sapper_result(&mut self, id: u8, cells: u32, mistakes: u8) -> bool {
+        if let Some(sapper) = self.get_game_mut().get_sapper_mut(id) {
+            sapper.cells = cells;
+            sapper.mistakes = mistakes;
+            return true;
+        } else {
+            return false;
+        }
+    }
+
+    fn on_sapper_time(&mut self, id: u8, time: u32) -> bool {
+        if let Some(sapper) = self.get_game_mut().get_sapper_mut(id) {
+            sapper.time = time;
             return true;
         } else {
             return false;
         }
     }
 
-    fn on_field_create(&mut self, _size: u8) -> bool {
+    fn on_field_create(
+        &mut self,
+        _width: u16,
+        _height: u16,
+        _topology: u8,
+        _is_wrapped: bool,
+    ) -> bool {
+        return true;
+    }
+
+    fn on_field_mines(&mut self, _count: u32) -> bool {
+        return true;
+    }
+
+    fn on_field_seed(&mut self, _seed: u32) -> bool {
+        return true;
+    }
+
+    fn on_cell_discover(&mut self, _position: u32, _mines_around: u8) -> bool {
+        return true;
+    }
+
+    fn on_cell_explode(&mut self, _position: u32) -> bool {
         return true;
     }
 
-    fn on_cell_discover(&mut self, _position: u16, _mines_around: u8) -> bool {
+    fn on_game_time(&mut self, time: u32, limit: u16) -> bool {
+        let game = self.get_game_mut();
+        game.set_time(time);
+        game.set_time_limit(u32::from(limit));
         return true;
     }
 
-    fn on_cell_explode(&mut self, _position: u16) -> bool {
+    fn on_game_over(&mut self, state: u8, winner: u8) -> bool {
+        self.get_game_mut().state = GameState::from_id(state, winner);
         return true;
     }
 
@@ -1,9 +1,11 @@
+use crate::cell::Mark;
 use crate::event::Event;
 use crate::event::EventData;
 use crate::event::EventManager;
 use crate::event::EVENT_SIZE;
 use crate::field::DiscoveryResult;
 use crate::game::Game;
+use crate::game::GameState;
 use crate::net::LocalMessage;
 use crate::net::Message;
 use crate::net::NetHandler;
@@ -27,11 +29,12 @@ use tokio::sync::mpsc::Sender;
 const CHANNELS_BUFFER_SIZE: usize = 128; // TODO: Learn more and tweak
 
 pub struct Server {
-    game: Game,
+    pub game: Game,
     sender: Sender<Message>,
     receiver: Receiver<Message>,
     clients: Vec<ServerClient>,
     thread: Option<JoinHandle<()>>,
+    cleared_by: Option<u8>,
     pub error: Option<String>,
 }
 
@@ -60,6 +63,7 @@ impl Server {
             receiver: server_receiver,
             clients: Vec::new(),
             thread: Some(thread),
+            cleared_by: None,
             error: None,
         });
     }
@@ -211,6 +215,171 @@ impl Server {
             }
         }
     }
+
+    // Every discovery which is still pending at the moment of the first one is treated as an
+    // opening too, so concurrent first clicks of different sappers get the same protection
+    fn generate_field(&mut self, position: u32) {
+        let mut openings = vec![position];
+
+        for event in self.game.events.iter() {
+            if let EventData::SapperDiscover { position, .. } = event.data {
+                openings.push(position);
+            }
+        }
+
+        self.game
+            .field
+            .generate_mines(&openings, &mut self.game.events);
+    }
+
+    // Takes a life and some score from a sapper who hit a mine, or kills it with the last life.
+    // Returns the score the sapper should have then
+    fn explode_sapper(&mut self, id: u8, score: u32) -> u32 {
+        let time = self.game.get_time();
+
+        let lives = match self.game.get_sapper_mut(id) {
+            Some(sapper) => {
+                sapper.mistakes = sapper.mistakes.saturating_add(1);
+                sapper.time = time;
+                sapper.lives
+            }
+            None => 0,
+        };
+
+        if lives > 1 {
+            self.game.events.fire(
+                EventData::SapperLives {
+                    id,
+                    lives: lives - 1,
+                },
+                None,
+                None,
+            );
+
+            return self.game.get_scoring().add_explosion(score);
+        } else {
+            self.game
+                .events
+                .fire(EventData::SapperDie { id }, None, None);
+
+            return score;
+        }
+    }
+
+    // Returns the score the sapper should have for the discovery
+    fn credit_cells(&mut self, id: u8, score: u32, count: usize) -> u32 {
+        if let Some(sapper) = self.game.get_sapper_mut(id) {
+            sapper.cells = sapper
+                .cells
+                .saturating_add(u32::try_from(count).unwrap_or(u32::MAX));
+        }
+
+        if self.cleared_by.is_none() && self.game.field.is_cleaned() {
+            self.cleared_by = Some(id);
+        }
+
+        return self.game.get_scoring().add_discovery(score, count);
+    }
+
+    // Settles the time, the flags and the clear bonus of every sapper, then announces the results
+    // along with the outcome, which is processed after them. The game is over for the server at
+    // once though, so the actions still queued are dropped rather than scored
+    fn finish_game(&mut self) {
+        let time = self.game.get_time();
+        let scoring = self.game.get_scoring();
+        let field = &self.game.field;
+
+        for sapper in &mut self.game.sappers {
+            if sapper.is_alive {
+                sapper.time = time;
+            }
+
+            let mut correct_flags = 0;
+            let mut wrong_flags = 0;
+
+            for (position, mark) in sapper.iter_marks() {
+                if mark == Mark::Flag {
+                    if field.is_mined(position) {
+                        correct_flags += 1;
+                    } else {
+                        wrong_flags += 1;
+                    }
+                }
+            }
+
+            sapper.mistakes = sapper
+                .mistakes
+                .saturating_add(u8::try_from(wrong_flags).unwrap_or(u8::MAX));
+
+            let mut score = scoring.add_flags(sapper.score, correct_flags, wrong_flags);
+
+            if self.cleared_by == Some(sapper.get_id()) {
+                score = scoring.add_clear(score);
+            }
+
+            if score != sapper.score {
+                sapper.score = score;
+
+                self.game.events.fire(
+                    EventData::SapperScore {
+                        id: sapper.get_id(),
+                        score,
+                    },
+                    None,
+                    None,
+                );
+            }
+        }
+
+        // The final bonuses may change the winner
+        let state = self.game.resolve_state();
+        self.game.state = state;
+
+        self.game.events.fire(
+            EventData::GameOver {
+                state: state.to_id(),
+                winner: state.get_winner(),
+            },
+            None,
+            None,
+        );
+
+        for sapper in &self.game.sappers {
+            for event in Self::get_result(sapper) {
+                self.game.events.fire(event, None, None);
+            }
+        }
+    }
+
+    fn can_act(&self, id: u8) -> bool {
+        return self.game.state == GameState::InProgress
+            && self
+                .game
+                .sappers
+                .iter()
+                .any(|s| s.get_id() == id && s.is_alive());
+    }
+
+    fn get_time(&self, time: u32) -> EventData {
+        return EventData::GameTime {
+            time,
+            limit: u16::try_from(self.game.get_time_limit()).unwrap_or(u16::MAX),
+        };
+    }
+
+    fn get_result(sapper: &Sapper) -> [EventData; 2] {
+        return [
+            EventData::SapperResult {
+                id: sapper.get_id(),
+                cells: sapper.cells,
+                mistakes: sapper.mistakes,
+            },
+            EventData::SapperTime {
+                id: sapper.get_id(),
+                time: sapper.time,
+            },
+        ];
+    }
 }
 
 impl Drop for Server {
@@ -282,6 +451,7 @@ impl NetHandler for Server {
         std::mem::swap(&mut self.clients, &mut clients);
     }
 
+    #[allow(clippy::too_many_lines)] // TODO: Resolve later
     fn on_sapper_connect(&mut self, address: SocketAddr) -> bool {
         let new_sapper_id;
 
@@ -291,12 +461,27 @@ impl NetHandler for Server {
             new_sapper_id = self.game.sappers.len() as u8;
         }
 
-        self.game.sappers.push(Sapper::new(
+        let mut new_sapper = Sapper::new(
             new_sapper_id,
             SapperBehavior::Remote,
             self.game.field.generate_random_position(),
             0.0,
-        ));
+        );
+
+        new_sapper.lives = self.game.get_lives();
+        self.game.sappers.push(new_sapper);
+
+        // Processed last, once the results of the finished game have arrived
+        if self.game.state != GameState::InProgress {
+            self.game.events.fire(
+                EventData::GameOver {
+                    state: self.game.state.to_id(),
+                    winner: self.game.state.get_winner(),
+                },
+                None,
+                Some(address),
+            );
+        }
 
         self.game.events.fire(
             EventData::SapperConnectResponse { id: new_sapper_id },
@@ -311,7 +496,22 @@ impl NetHandler for Server {
                 Some(address)
             };
 
-            if !sapper.is_alive {
+            if self.game.state != GameState::InProgress {
+                for event in Self::get_result(sapper) {
+                    self.game.events.fire(event, None, target);
+                }
+            }
+
+            if sapper.is_alive {
+                self.game.events.fire(
+                    EventData::SapperLives {
+                        id: sapper.get_id(),
+                        lives: sapper.lives,
+                    },
+                    None,
+                    target,
+                );
+            } else {
                 self.game.events.fire(
                     EventData::SapperDie {
                         id: sapper.get_id(),
@@ -342,15 +542,7 @@ impl NetHandler for Server {
             );
         }
 
-        for (i, cell) in self.game.field.get_cells().iter().enumerate() {
-            let position = match u16::try_from(i) {
-                Ok(position) => position,
-                Err(_) => {
-                    // TODO: Log error
-                    break;
-                }
-            };
-
+        for (position, cell) in self.game.field.iter_cells() {
             if let Some(mines_around) = cell.mines_around {
                 self.game.events.fire(
                     EventData::CellDiscover {
@@ -369,9 +561,34 @@ impl NetHandler for Server {
             }
         }
 
+        self.game
+            .events
+            .fire(self.get_time(self.game.get_time()), None, Some(address));
+
+        self.game.events.fire(
+            EventData::FieldSeed {
+                seed: self.game.field.get_seed(),
+            },
+            None,
+            Some(address),
+        );
+
+        if self.game.field.is_mines_count_known() {
+            self.game.events.fire(
+                EventData::FieldMines {
+                    count: u32::try_from(self.game.field.get_mines_count()).unwrap_or(u32::MAX),
+                },
+                None,
+                Some(address),
+            );
+        }
+
         self.game.events.fire(
             EventData::FieldCreate {
-                size: self.game.field.get_size(),
+                width: self.game.field.get_width(),
+                height: self.game.field.get_height(),
+                topology: self.game.field.get_topology().get_id(),
+                is_wrapped: self.game.field.is_wrapped(),
             },
             None,
             Some(address),
@@ -380,10 +597,10 @@ impl NetHandler for Server {
         return true;
     }
 
-    fn on_sapper_discover(&mut self, id: u8, position: u16) -> bool {
+    fn on_sapper_discover(&mut self, id: u8, position: u32) -> bool {
         struct SapperData {
             id: u8,
-            score: u16,
+            score: u32,
         }
 
         let mut sapper_data = None;
@@ -396,21 +613,44 @@ impl NetHandler for Server {
         }
 
         if let Some(sapper_data) = sapper_data {
+            // A late discovery of a dead sapper or of a finished game is dropped
+            if !self.can_act(id) {
+                return true;
+            }
+
+            if !self.game.field.is_generated() {
+                self.generate_field(position);
+            }
+
+            self.game.start_clock();
+
             match self.game.field.discover(position, &mut self.game.events) {
-                DiscoveryResult::Success => {
+                DiscoveryResult::Success(revealed) => {
+                    log::debug!("Sapper {} revealed {} cells", id, revealed.len());
+                    let score = self.credit_cells(id, sapper_data.score, revealed.len());
+
                     self.game.events.fire(
                         EventData::SapperScore {
                             id: sapper_data.id,
-                            score: sapper_data.score + 1,
+                            score,
                         },
                         None,
                         None,
                     );
                 }
                 DiscoveryResult::Failure => {
-                    self.game
-                        .events
-                        .fire(EventData::SapperDie { id: sapper_data.id }, None, None);
+                    let score = self.explode_sapper(sapper_data.id, sapper_data.score);
+
+                    if score != sapper_data.score {
+                        self.game.events.fire(
+                            EventData::SapperScore {
+                                id: sapper_data.id,
+                                score,
+                            },
+                            None,
+                            None,
+                        );
+                    }
                 }
                 DiscoveryResult::AlreadyDiscovered => {}
             }
@@ -421,6 +661,76 @@ impl NetHandler for Server {
         }
     }
 
+    fn on_sapper_chord(&mut self, id: u8, position: u32) -> bool {
+        let (positions, score) = match self.game.sappers.iter().find(|s| s.get_id() == id) {
+            Some(sapper) => (
+                sapper.get_chord_positions(&self.game.field, position),
+                sapper.score,
+            ),
+            None => return false,
+        };
+
+        // A late chord of a dead sapper or of a finished game is dropped
+        if !self.can_act(id) {
+            return true;
+        }
+
+        self.game.start_clock();
+
+        let mut score_new = score;
+
+        // A wrong mark makes the chord hit a mine, which costs the sapper a life
+        for position in positions {
+            match self.game.field.discover(position, &mut self.game.events) {
+                DiscoveryResult::Success(revealed) => {
+                    log::debug!("Sapper {} revealed {} cells by chord", id, revealed.len());
+                    score_new = self.credit_cells(id, score_new, revealed.len());
+                }
+                DiscoveryResult::Failure => {
+                    score_new = self.explode_sapper(id, score_new);
+                    break;
+                }
+                DiscoveryResult::AlreadyDiscovered => {}
+            }
+        }
+
+        if score_new != score {
+            self.game.events.fire(
+                EventData::SapperScore {
+                    id,
+                    score: score_new,
+                },
+                None,
+                None,
+            );
+        }
+
+        return true;
+    }
+
+    // The server has counted the results itself, these events only tell the clients about them
+    fn on_sapper_result(&mut self, _id: u8, _cells: u32, _mistakes: u8) -> bool {
+        return true;
+    }
+
+    fn on_sapper_time(&mut self, _id: u8, _time: u32) -> bool {
+        return true;
+    }
+
+    fn after_update(&mut self) {
+        if self.game.state == GameState::InProgress {
+            let clock = self.game.get_clock();
+
+            if clock != self.game.get_time() {
+                self.game.events.fire(self.get_time(clock), None, None);
+            }
+
+            if self.game.resolve_state() != GameState::InProgress {
+                self.finish_game();
+            }
+        }
+    }
+
     fn get_game_mut(&mut self) -> &mut Game {
         return &mut self.game;
     }
@@ -433,3 +743,91 @@ impl NetHandler for Server {
         return true;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::Field;
+    use crate::field::MinesAmount;
+    use std::collections::HashSet;
+
+    fn new_server(mines: HashSet<u32>, lives: u8) -> Server {
+        let mut field = Field::new(4, 1, MinesAmount::Count(0), 0);
+        field.set_mines(mines);
+
+        let sappers = vec![
+            Sapper::new(0, SapperBehavior::Remote, 0, 0.0),
+            Sapper::new(1, SapperBehavior::Remote, 0, 0.0),
+        ];
+
+        let mut game = Game::new(field, sappers);
+        game.set_lives(lives);
+
+        return block_on(Server::new(SocketAddr::from(([127, 0, 0, 1], 0)), game)).unwrap();
+    }
+
+    #[test]
+    fn test_late_discovery() {
+        let mut server = new_server(HashSet::from([3]), 1);
+        server.game.set_time_limit(1);
+        server.game.set_time(1);
+        server.update(None);
+        assert_eq!(server.game.state, GameState::Draw, "Time is over");
+
+        // Queued after the end but processed before the outcome is announced
+        server
+            .game
+            .events
+            .fire(EventData::SapperDiscover { id: 1, position: 0 }, None, None);
+
+        server.update(None);
+        assert_eq!(server.game.state, GameState::Draw, "Same outcome");
+
+        for sapper in &server.game.sappers {
+            assert_eq!(sapper.score, 0, "Not scored, sapper {}", sapper.get_id());
+        }
+    }
+
+    #[test]
+    fn test_explosion() {
+        let mut server = new_server(HashSet::from([2, 3]), 2);
+        server.game.sappers.iter_mut().for_each(|s| s.score = 15);
+
+        // A hit with a life left costs score
+        assert!(server.on_sapper_discover(0, 3), "Processed");
+        assert!(
+            server
+                .game
+                .events
+                .iter()
+                .any(|e| matches!(e.data, EventData::SapperLives { id: 0, lives: 1 })),
+            "Life taken"
+        );
+
+        server.update(None);
+
+        if let Some(sapper) = server.game.get_sapper_mut(0) {
+            assert!(sapper.is_alive(), "Survived");
+            assert_eq!(sapper.lives, 1, "Lives");
+            assert_eq!(sapper.score, 5, "Penalty");
+        }
+
+        // The last life is not charged, the sapper dies instead
+        assert!(server.on_sapper_discover(0, 2), "Processed again");
+        assert!(
+            server
+                .game
+                .events
+                .iter()
+                .any(|e| matches!(e.data, EventData::SapperDie { id: 0 })),
+            "Death"
+        );
+
+        server.update(None);
+
+        if let Some(sapper) = server.game.get_sapper_mut(0) {
+            assert!(!sapper.is_alive(), "Dead");
+            assert_eq!(sapper.score, 5, "No penalty");
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use crate::field::MinesAmount;
+use serde::Deserialize;
+use serde::Serialize;
+use std::path::Path;
+
+pub const CUSTOM: &str = "Custom";
+
+// A named set of the field size, mines and bots settings
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    pub width: u16,
+    pub height: u16,
+    pub mines_amount: MinesAmount,
+    pub bots: u8,
+    pub bots_reaction: f64,
+}
+
+impl Profile {
+    fn new(
+        name: &str,
+        width: u16,
+        height: u16,
+        mines_amount: MinesAmount,
+        bots: u8,
+        bots_reaction: f64,
+    ) -> Self {
+        return Self {
+            name: name.to_owned(),
+            width,
+            height,
+            mines_amount,
+            bots,
+            bots_reaction,
+        };
+    }
+
+    // Whether the settings match the profile, regardless of its name
+    pub fn is_same(&self, other: &Self) -> bool {
+        let is_same_mines = match (self.mines_amount, other.mines_amount) {
+            (MinesAmount::Count(a), MinesAmount::Count(b)) => a == b,
+            (MinesAmount::Density(a), MinesAmount::Density(b)) => (a - b).abs() < 0.001,
+            _ => false,
+        };
+
+        return is_same_mines
+            && self.width == other.width
+            && self.height == other.height
+            && self.bots == other.bots
+            && (self.bots_reaction - other.bots_reaction).abs() < 0.001;
+    }
+}
+
+pub fn get_builtin() -> Vec<Profile> {
+    return vec![
+        Profile::new("Beginner", 9, 9, MinesAmount::Count(10), 1, 2.0),
+        Profile::new("Intermediate", 16, 16, MinesAmount::Count(40), 2, 1.0),
+        Profile::new("Expert", 30, 16, MinesAmount::Count(99), 3, 0.5),
+    ];
+}
+
+// A missing or broken file just means there are no user profiles yet
+pub fn load(path: &Path) -> Vec<Profile> {
+    let data = match std::fs::read(path) {
+        Ok(data) => data,
+        Err(_) => return Vec::new(),
+    };
+
+    match serde_json::from_slice(&data) {
+        Ok(profiles) => {
+            return profiles;
+        }
+        Err(error) => {
+            log::warn!("Failed to parse profiles ({}): {}", path.display(), error);
+            return Vec::new();
+        }
+    }
+}
+
+pub fn save(path: &Path, profiles: &[Profile]) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(profiles).map_err(|e| format!("{}", e))?;
+
+    return std::fs::write(path, data)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir().join(format!("{}-profiles-test.json", std::process::id()));
+        let profiles = get_builtin();
+
+        save(&path, &profiles).unwrap();
+        let loaded = load(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.len(), profiles.len(), "Count");
+
+        for (a, b) in profiles.iter().zip(&loaded) {
+            assert_eq!(a.name, b.name, "Name");
+            assert!(a.is_same(b), "Settings");
+        }
+
+        assert!(
+            !profiles.first().unwrap().is_same(profiles.last().unwrap()),
+            "Different"
+        );
+        assert!(load(&path).is_empty(), "Missing file");
+    }
+}
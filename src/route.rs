@@ -0,0 +1,95 @@
+use crate::field::Field;
+
+// Bigger routes take too long to improve, the farther targets wait for the next plan
+const TARGETS_MAX: usize = 32;
+const IMPROVEMENTS_MAX: usize = 8;
+
+// Orders the nearest targets to visit them all in a short walk from the start. The nearest
+// unvisited target goes next at first, then the crossing legs of the walk are untangled
+pub fn plan(field: &Field, start: u32, mut targets: Vec<u32>) -> Vec<u32> {
+    targets.sort_by_key(|p| (field.to_distance(start, *p), *p));
+    targets.truncate(TARGETS_MAX);
+
+    let mut route = Vec::with_capacity(targets.len());
+    let mut position = start;
+
+    while let Some((i, _)) = targets
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, p)| (field.to_distance(position, **p), **p))
+    {
+        position = targets.swap_remove(i);
+        route.push(position);
+    }
+
+    for _ in 0..IMPROVEMENTS_MAX {
+        if !improve(field, start, &mut route) {
+            break;
+        }
+    }
+
+    return route;
+}
+
+// Reverses every part of the route which makes it shorter then, returns whether any did
+fn improve(field: &Field, start: u32, route: &mut [u32]) -> bool {
+    let distance = |a: Option<u32>, b: Option<u32>| match (a, b) {
+        (Some(a), Some(b)) => return field.to_distance(a, b),
+        _ => return 0,
+    };
+
+    let mut is_improved = false;
+
+    for i in 0..route.len() {
+        let before = if i == 0 {
+            Some(start)
+        } else {
+            route.get(i - 1).copied()
+        };
+
+        for j in (i + 1)..route.len() {
+            let first = route.get(i).copied();
+            let last = route.get(j).copied();
+            let after = route.get(j + 1).copied();
+
+            if distance(before, last) + distance(first, after)
+                < distance(before, first) + distance(last, after)
+            {
+                if let Some(part) = route.get_mut(i..=j) {
+                    part.reverse();
+                }
+
+                is_improved = true;
+            }
+        }
+    }
+
+    return is_improved;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::MinesAmount;
+
+    #[test]
+    fn test_plan() {
+        // Going for the nearest target every time would leave the farthest one behind
+        let field = Field::new(9, 1, MinesAmount::Count(0), 0);
+        let route = plan(&field, 4, vec![0, 5, 8, 3]);
+
+        let mut sorted = route.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, vec![0, 3, 5, 8], "Every target");
+
+        let mut length = 0;
+        let mut position = 4;
+
+        for target in &route {
+            length += field.to_distance(position, *target);
+            position = *target;
+        }
+
+        assert_eq!(length, 12, "Length");
+    }
+}
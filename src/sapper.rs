@@ -1,17 +1,30 @@
 use crate::cell::Cell;
+use crate::cell::Mark;
 use crate::event::EventData;
 use crate::event::EventManager;
 use crate::field::Field;
+use crate::field::MinesAmount;
+use crate::route;
+use crate::skill::Skill;
+use crate::solver;
+use crate::solver::MinesLeft;
+use crate::task_board::TaskBoard;
+use crate::topology::Direction;
+use crate::utils;
 use crate::utils::Timer;
+use rand::prelude::*;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::time::Duration;
 use termwiz::input::InputEvent;
 use termwiz::input::KeyCode;
 use termwiz::input::KeyEvent;
 
-const NAME_PLAYER: &str = "YOU";
+pub const NAME_PLAYER: &str = "YOU";
 const NAME_REMOTE: &str = "NET";
 const NAME_BOT: &str = "BOT";
+const GUESS_RISK_CAUTIOUS: f64 = 0.2;
+const GUESS_SEARCH_MAX: usize = 4096; // Cells to look through for an unconstrained one
 
 pub enum SapperBehavior {
     Player,
@@ -19,37 +32,90 @@ pub enum SapperBehavior {
     Bot,
 }
 
+// How a bot acts once nothing is certain. A cautious one waits for the others to reveal more
+// unless the guess is quite safe
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Guessing {
+    Never,
+    Cautious,
+    Always,
+}
+
+// Whether a bot works alone or shares a board of claimed targets with the other cooperative bots
+#[derive(Clone, Copy, PartialEq)]
+pub enum Teamwork {
+    Competitive,
+    Cooperative,
+}
+
+// Where another sapper stands and which cell it's heading to
+pub struct Rival {
+    pub id: u8,
+    pub position: u32,
+    pub target: Option<u32>,
+}
+
 pub struct Sapper {
     id: u8,
-    pub position: u16,
+    pub position: u32,
     pub is_alive: bool,
+    pub lives: u8,
     pub behavior: SapperBehavior,
-    marks: HashSet<u16>,
+    marks: HashMap<u32, Mark>,
+    is_question_blocking: bool,
+    guessing: Guessing,
+    skill: Skill,
+    is_avoiding_rivals: bool,
+    teamwork: Teamwork,
+    route: Vec<BotTask>,
+    target: Option<u32>,
     timer: Timer,
-    pub score: u16,
+    pub score: u32,
+    pub cells: u32,
+    pub mistakes: u8,
+    pub time: u32,
     events: EventManager,
 }
 
+#[derive(Clone, Copy, PartialEq)]
 struct BotTask {
-    position: u16,
+    position: u32,
     is_mined: bool,
 }
 
 impl Sapper {
-    pub fn new(id: u8, behavior: SapperBehavior, position: u16, reaction: f64) -> Self {
+    pub fn new(id: u8, behavior: SapperBehavior, position: u32, reaction: f64) -> Self {
         return Self {
             id,
             position,
             is_alive: true,
+            lives: 1,
             behavior,
-            marks: HashSet::new(),
+            marks: HashMap::new(),
+            is_question_blocking: true,
+            guessing: Guessing::Always,
+            skill: Skill::DEFAULT,
+            is_avoiding_rivals: false,
+            teamwork: Teamwork::Competitive,
+            route: Vec::new(),
+            target: None,
             timer: Timer::new(Duration::from_secs_f64(reaction)),
             score: 0,
+            cells: 0,
+            mistakes: 0,
+            time: 0,
             events: EventManager::new(),
         };
     }
 
-    pub fn update(&mut self, field: &mut Field, input: Option<&InputEvent>) {
+    pub fn update(
+        &mut self,
+        field: &Field,
+        input: Option<&InputEvent>,
+        rivals: &[Rival],
+        task_board: &mut TaskBoard,
+        rng: &mut StdRng,
+    ) {
         self.remove_useless_marks(field); // TODO: Try to optimize
 
         if !self.is_alive {
@@ -64,36 +130,58 @@ impl Sapper {
             }
             SapperBehavior::Remote => {}
             SapperBehavior::Bot => {
-                self.update_as_bot(field);
+                self.update_as_bot(field, rivals, task_board, rng);
             }
         }
     }
 
-    fn update_as_player(&mut self, field: &mut Field, input: &InputEvent) {
+    fn update_as_player(&mut self, field: &Field, input: &InputEvent) {
         match input {
             InputEvent::Key(KeyEvent {
                 key: KeyCode::UpArrow,
                 ..
             }) => {
-                self.shift_position(0, -1, field);
+                self.step(Direction::Up, field);
             }
             InputEvent::Key(KeyEvent {
                 key: KeyCode::DownArrow,
                 ..
             }) => {
-                self.shift_position(0, 1, field);
+                self.step(Direction::Down, field);
             }
             InputEvent::Key(KeyEvent {
                 key: KeyCode::LeftArrow,
                 ..
             }) => {
-                self.shift_position(-1, 0, field);
+                self.step(Direction::Left, field);
             }
             InputEvent::Key(KeyEvent {
                 key: KeyCode::RightArrow,
                 ..
             }) => {
-                self.shift_position(1, 0, field);
+                self.step(Direction::Right, field);
+            }
+            InputEvent::Key(KeyEvent {
+                key: KeyCode::Home, ..
+            }) => {
+                self.step(Direction::UpLeft, field);
+            }
+            InputEvent::Key(KeyEvent {
+                key: KeyCode::PageUp,
+                ..
+            }) => {
+                self.step(Direction::UpRight, field);
+            }
+            InputEvent::Key(KeyEvent {
+                key: KeyCode::End, ..
+            }) => {
+                self.step(Direction::DownLeft, field);
+            }
+            InputEvent::Key(KeyEvent {
+                key: KeyCode::PageDown,
+                ..
+            }) => {
+                self.step(Direction::DownRight, field);
             }
             InputEvent::Key(KeyEvent {
                 key: KeyCode::Char('m'),
@@ -107,106 +195,251 @@ impl Sapper {
             }) => {
                 self.discover(field);
             }
+            InputEvent::Key(KeyEvent {
+                key: KeyCode::Char('c'),
+                ..
+            }) => {
+                self.chord(field);
+            }
             _ => {}
         }
     }
 
-    fn update_as_bot(&mut self, field: &mut Field) {
+    // A cooperative bot claims every target it's going to, and doesn't risk a guess while the
+    // others still have certain work which may reveal more
+    fn update_as_bot(
+        &mut self,
+        field: &Field,
+        rivals: &[Rival],
+        task_board: &mut TaskBoard,
+        rng: &mut StdRng,
+    ) {
         if self.timer.next_if_is_done() {
-            if let Some(task) = self.find_task(field) {
-                self.perform_task(&task, field);
+            let is_cooperative = self.teamwork == Teamwork::Cooperative;
+
+            if is_cooperative {
+                task_board.release(self.id);
             }
-        }
-    }
 
-    fn find_task(&mut self, field: &mut Field) -> Option<BotTask> {
-        let mut task = None;
-        let mut task_distance = u16::MAX;
+            let task = self.find_task(field, rivals, task_board).or_else(|| {
+                if is_cooperative && task_board.has_claims_of_other(self.id) {
+                    return None;
+                }
+
+                return self.guess(field);
+            });
+
+            self.target = task.map(|t| t.position);
 
-        for (i, cell) in field.get_cells().iter().enumerate() {
-            let cell_position = match u16::try_from(i) {
-                Ok(cell_position) => cell_position,
-                Err(_) => {
-                    // TODO: Log error
-                    break;
+            if is_cooperative {
+                for position in self.route.iter().map(|t| t.position).chain(self.target) {
+                    task_board.claim(position, self.id);
                 }
-            };
+            }
 
-            if !cell.is_discovered() {
-                continue;
+            if let Some(task) = task {
+                self.perform_task(task, field, rivals, rng);
             }
+        }
+    }
 
-            let mines_around = cell.mines_around.unwrap_or(0);
-            let mut undiscovered = Vec::with_capacity(8);
-            let mut mines_found = 0;
-
-            for cell_near_position in field.around(cell_position, false) {
-                if let Some(cell_near) = field.get_cell(cell_near_position) {
-                    if cell_near.is_exploded || self.has_marked(cell_near_position) {
-                        mines_found += 1;
-                    } else if !cell_near.is_discovered() {
-                        undiscovered.push(cell_near_position);
-                    }
-                }
+    // Follows a route through the certain cells. The current target is kept while it's still
+    // certain, and the rest of the route is planned again from it since the certain cells change
+    // with every discovery
+    fn find_task(
+        &mut self,
+        field: &Field,
+        rivals: &[Rival],
+        task_board: &TaskBoard,
+    ) -> Option<BotTask> {
+        let solution =
+            solver::solve_field(field, |p| self.has_flagged(p), self.skill.get_reasoning());
+
+        let mut tasks = solution
+            .safe
+            .iter()
+            .map(|p| (*p, false))
+            .chain(solution.mined.iter().map(|p| (*p, true)))
+            .map(|(position, is_mined)| BotTask { position, is_mined })
+            .collect::<Vec<_>>();
+
+        // Rivals keep their targets unless there's nothing else to do
+        if self.is_avoiding_rivals {
+            let tasks_free = tasks
+                .iter()
+                .filter(|t| !self.is_targeted_by_rivals(t.position, rivals))
+                .copied()
+                .collect::<Vec<_>>();
+
+            if !tasks_free.is_empty() {
+                tasks = tasks_free;
+            }
+        }
+
+        if self.teamwork == Teamwork::Cooperative {
+            tasks.retain(|t| !task_board.is_claimed_by_other(t.position, self.id));
+        }
+
+        let head = self.route.first().filter(|t| tasks.contains(t)).copied();
+        let start = head.map_or(self.position, |t| t.position);
+
+        let positions = tasks
+            .iter()
+            .filter(|t| Some(**t) != head)
+            .map(|t| t.position)
+            .collect::<Vec<_>>();
+
+        self.route = head
+            .into_iter()
+            .chain(
+                route::plan(field, start, positions)
+                    .into_iter()
+                    .filter_map(|p| tasks.iter().find(|t| t.position == p).copied()),
+            )
+            .collect();
+
+        return self.route.first().copied();
+    }
+
+    // Picks the cell which is the least likely to be mined, the nearest one of the equally risky
+    fn guess(&self, field: &Field) -> Option<BotTask> {
+        let risk_max = match std::cmp::min(self.guessing, self.skill.get_guessing()) {
+            Guessing::Never => return None,
+            Guessing::Cautious => GUESS_RISK_CAUTIOUS,
+            Guessing::Always => 1.0,
+        };
+
+        let exploded = field.iter_cells().filter(|(_, c)| c.is_exploded).count();
+        let mines_found = exploded + self.get_flags_count();
+
+        let mines_left = if field.is_mines_count_known() {
+            MinesLeft::Count {
+                mines: field.get_mines_count().saturating_sub(mines_found),
+                cells: field
+                    .get_cells_count()
+                    .saturating_sub(field.get_cells_discovered_count() + mines_found),
             }
+        } else if let MinesAmount::Density(density) = field.get_mines_amount() {
+            MinesLeft::Density(density)
+        } else {
+            return None;
+        };
 
-            let unmarked = mines_around.saturating_sub(mines_found);
-            let is_mined = undiscovered.len() == usize::from(unmarked);
+        let probabilities = solver::get_probabilities(field, |p| self.has_flagged(p), mines_left);
 
-            if mines_around == mines_found || is_mined {
-                for undiscovered_position in &undiscovered {
-                    let distance_test = field.to_distance(self.position, *undiscovered_position);
+        let unconstrained = probabilities.unconstrained.and_then(|chance| {
+            return self
+                .find_unconstrained(field, &probabilities.frontier)
+                .map(|p| (p, chance));
+        });
+
+        return probabilities
+            .frontier
+            .iter()
+            .map(|(p, chance)| (*p, *chance))
+            .chain(unconstrained)
+            .filter(|(_, chance)| *chance <= risk_max)
+            .min_by(|(a, a_chance), (b, b_chance)| {
+                return a_chance
+                    .partial_cmp(b_chance)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| {
+                        return field
+                            .to_distance(self.position, *a)
+                            .cmp(&field.to_distance(self.position, *b));
+                    })
+                    .then_with(|| a.cmp(b));
+            })
+            .map(|(position, _)| BotTask {
+                position,
+                is_mined: false,
+            });
+    }
 
-                    if task_distance > distance_test {
-                        task_distance = distance_test;
+    // The nearest unknown cell which none of the discovered numbers tells anything about
+    fn find_unconstrained(&self, field: &Field, frontier: &HashMap<u32, f64>) -> Option<u32> {
+        let mut visited = HashSet::from([self.position]);
+        let mut pending = vec![self.position];
+        let mut next = 0;
 
-                        task = Some(BotTask {
-                            position: *undiscovered_position,
-                            is_mined,
-                        });
-                    }
+        while let Some(position) = pending.get(next).copied() {
+            next += 1;
+
+            let is_unknown = field
+                .get_cell(position)
+                .map_or(false, |c| !c.is_discovered() && !c.is_exploded);
+
+            if is_unknown && !self.has_flagged(position) && !frontier.contains_key(&position) {
+                return Some(position);
+            }
+
+            for position_near in field.around(position, false) {
+                if visited.len() < GUESS_SEARCH_MAX && visited.insert(position_near) {
+                    pending.push(position_near);
                 }
             }
         }
 
-        return task;
+        return None;
     }
 
-    fn perform_task(&mut self, task: &BotTask, field: &mut Field) {
+    fn perform_task(&mut self, task: BotTask, field: &Field, rivals: &[Rival], rng: &mut StdRng) {
         if self.position == task.position {
+            if utils::is_chance(rng, self.skill.get_error_chance()) {
+                self.step_randomly(field, rng);
+            }
+
             if task.is_mined {
                 self.toggle_mark(field);
             } else {
                 self.discover(field);
             }
         } else {
-            self.move_to(task.position, field);
+            self.move_to(task.position, field, rivals, rng);
         }
     }
 
-    fn move_to(&mut self, target: u16, field: &Field) {
-        let (x, y) = field.to_coordinate(self.position);
-        let (target_x, target_y) = field.to_coordinate(target);
-        let mut shift_x = 0;
-        let mut shift_y = 0;
-
-        if x < target_x {
-            shift_x += 1;
-        } else if x > target_x {
-            shift_x -= 1;
-        } else if y < target_y {
-            shift_y += 1;
-        } else if y > target_y {
-            shift_y -= 1;
+    // Takes the step which gets the closest to the target. Of the equally good steps, the one
+    // which doesn't bump into a rival is preferred when avoiding them
+    fn move_to(&mut self, target: u32, field: &Field, rivals: &[Rival], rng: &mut StdRng) {
+        if utils::is_chance(rng, self.skill.get_wander_chance()) {
+            self.step_randomly(field, rng);
+            return;
+        }
+
+        let distance = field.to_distance(self.position, target);
+        let mut direction = None;
+        let mut cost = None;
+
+        for direction_test in Direction::ALL {
+            if let Some(position) = field.step(self.position, direction_test) {
+                let is_blocked = self.is_avoiding_rivals
+                    && rivals
+                        .iter()
+                        .any(|r| r.id != self.id && r.position == position);
+
+                let cost_test = (field.to_distance(position, target), is_blocked);
+
+                if cost_test.0 < distance && cost.map_or(true, |c| c > cost_test) {
+                    cost = Some(cost_test);
+                    direction = Some(direction_test);
+                }
+            }
         }
 
-        if shift_x != 0 || shift_y != 0 {
-            self.shift_position(shift_x, shift_y, field);
+        if let Some(direction) = direction {
+            self.step(direction, field);
         }
     }
 
-    fn shift_position(&mut self, x: i32, y: i32, field: &Field) {
-        if let Some(position) = field.move_position(self.position, x, y) {
+    fn step_randomly(&mut self, field: &Field, rng: &mut StdRng) {
+        if let Some(direction) = Direction::ALL.choose(rng) {
+            self.step(*direction, field);
+        }
+    }
+
+    fn step(&mut self, direction: Direction, field: &Field) {
+        if let Some(position) = field.step(self.position, direction) {
             self.position = position;
             self.events.fire(
                 EventData::SapperMove {
@@ -222,33 +455,49 @@ impl Sapper {
     fn toggle_mark(&mut self, field: &Field) {
         let i = self.position;
 
-        if !self.marks.remove(&i) && field.get_cell(i).map_or(false, Cell::is_markable) {
-            self.marks.insert(i);
+        if !field.get_cell(i).map_or(false, Cell::is_markable) {
+            return;
+        }
+
+        let mark = Mark::next(self.get_mark(i));
+        self.set_mark(i, mark);
+
+        self.events.fire(
+            EventData::SapperMark {
+                id: self.id,
+                position: i,
+                mark: Mark::to_id(mark),
+            },
+            None,
+            None,
+        );
+    }
+
+    pub fn set_mark(&mut self, position: u32, mark: Option<Mark>) {
+        if let Some(mark) = mark {
+            self.marks.insert(position, mark);
+        } else {
+            self.marks.remove(&position);
         }
     }
 
     fn remove_useless_marks(&mut self, field: &Field) {
-        self.marks = self
-            .marks
-            .iter()
-            .filter_map(|i| {
-                let i = *i;
-
-                if field.get_cell(i).map_or(false, Cell::is_markable) {
-                    return Some(i);
-                } else {
-                    return None;
-                }
-            })
-            .collect();
+        self.marks
+            .retain(|i, _| field.get_cell(*i).map_or(false, Cell::is_markable));
     }
 
-    pub fn discover(&mut self, field: &mut Field) {
+    pub fn discover(&mut self, field: &Field) {
         let can_discover = field
             .get_cell(self.position)
             .map_or(false, |c| !c.is_discovered() && !c.is_exploded);
 
-        if can_discover && !self.has_marked(self.position) {
+        let is_blocked = match self.get_mark(self.position) {
+            Some(Mark::Flag) => true,
+            Some(Mark::Question) => self.is_question_blocking,
+            None => false,
+        };
+
+        if can_discover && !is_blocked {
             self.events.fire(
                 EventData::SapperDiscover {
                     id: self.id,
@@ -260,8 +509,87 @@ impl Sapper {
         }
     }
 
-    pub fn has_marked(&self, position: u16) -> bool {
-        return self.marks.contains(&position);
+    pub fn chord(&mut self, field: &Field) {
+        if !self.get_chord_positions(field, self.position).is_empty() {
+            self.events.fire(
+                EventData::SapperChord {
+                    id: self.id,
+                    position: self.position,
+                },
+                None,
+                None,
+            );
+        }
+    }
+
+    // Returns the unflagged neighbors of a discovered number which has got as many flagged or
+    // exploded neighbors as its number says, the rest of the neighbors are supposed to be safe
+    pub fn get_chord_positions(&self, field: &Field, position: u32) -> Vec<u32> {
+        let mines_around = match field.get_cell(position).and_then(|c| c.mines_around) {
+            Some(mines_around) => mines_around,
+            None => return Vec::new(),
+        };
+
+        let mut positions = Vec::with_capacity(8);
+        let mut mines_found = 0;
+
+        for position_near in field.around(position, false) {
+            if let Some(cell_near) = field.get_cell(position_near) {
+                if cell_near.is_exploded || self.has_flagged(position_near) {
+                    mines_found += 1;
+                } else if !cell_near.is_discovered() {
+                    positions.push(position_near);
+                }
+            }
+        }
+
+        if mines_found == mines_around {
+            return positions;
+        } else {
+            return Vec::new();
+        }
+    }
+
+    pub fn get_mark(&self, position: u32) -> Option<Mark> {
+        return self.marks.get(&position).copied();
+    }
+
+    pub fn iter_marks(&self) -> impl Iterator<Item = (u32, Mark)> + '_ {
+        return self.marks.iter().map(|(position, mark)| (*position, *mark));
+    }
+
+    pub fn has_flagged(&self, position: u32) -> bool {
+        return self.get_mark(position) == Some(Mark::Flag);
+    }
+
+    pub fn set_question_blocking(&mut self, is_question_blocking: bool) {
+        self.is_question_blocking = is_question_blocking;
+    }
+
+    pub fn set_guessing(&mut self, guessing: Guessing) {
+        self.guessing = guessing;
+    }
+
+    pub fn set_skill(&mut self, skill: Skill) {
+        self.skill = skill;
+    }
+
+    pub fn set_avoiding_rivals(&mut self, is_avoiding_rivals: bool) {
+        self.is_avoiding_rivals = is_avoiding_rivals;
+    }
+
+    pub fn set_teamwork(&mut self, teamwork: Teamwork) {
+        self.teamwork = teamwork;
+    }
+
+    fn is_targeted_by_rivals(&self, position: u32, rivals: &[Rival]) -> bool {
+        return rivals
+            .iter()
+            .any(|r| r.id != self.id && r.target == Some(position));
+    }
+
+    pub const fn get_target(&self) -> Option<u32> {
+        return self.target;
     }
 
     pub const fn is_player(&self) -> bool {
@@ -280,7 +608,7 @@ impl Sapper {
         return self.is_alive;
     }
 
-    pub const fn get_position(&self) -> u16 {
+    pub const fn get_position(&self) -> u32 {
         return self.position;
     }
 
@@ -292,11 +620,15 @@ impl Sapper {
         };
     }
 
-    pub fn get_marks_count(&self) -> usize {
-        return self.marks.len();
+    pub fn get_flags_count(&self) -> usize {
+        return self.marks.values().filter(|m| **m == Mark::Flag).count();
+    }
+
+    pub const fn get_lives(&self) -> u8 {
+        return self.lives;
     }
 
-    pub const fn get_score(&self) -> u16 {
+    pub const fn get_score(&self) -> u32 {
         return self.score;
     }
 
@@ -304,3 +636,89 @@ impl Sapper {
         return &mut self.events;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::MinesAmount;
+
+    #[test]
+    fn test_get_chord_positions() {
+        let mut field = Field::new(3, 3, MinesAmount::Count(0), 0);
+        let mut sapper = Sapper::new(0, SapperBehavior::Player, 4, 0.0);
+
+        if let Some(cell) = field.get_cell_mut(4) {
+            cell.mines_around = Some(2);
+        }
+
+        if let Some(cell) = field.get_cell_mut(1) {
+            cell.mines_around = Some(1);
+        }
+
+        assert!(sapper.get_chord_positions(&field, 4).is_empty(), "Unmarked");
+        assert!(
+            sapper.get_chord_positions(&field, 0).is_empty(),
+            "Undiscovered"
+        );
+
+        sapper.set_mark(0, Some(Mark::Flag));
+        assert!(
+            sapper.get_chord_positions(&field, 4).is_empty(),
+            "Partially marked"
+        );
+
+        sapper.set_mark(8, Some(Mark::Question));
+        assert!(
+            sapper.get_chord_positions(&field, 4).is_empty(),
+            "Questioned"
+        );
+
+        sapper.set_mark(8, Some(Mark::Flag));
+        assert_eq!(
+            sapper.get_chord_positions(&field, 4),
+            vec![2, 3, 5, 6, 7],
+            "Satisfied"
+        );
+    }
+
+    #[test]
+    fn test_guess() {
+        // Nothing is discovered yet, so a bot has to guess right where it stands
+        let field = Field::new(3, 3, MinesAmount::Count(1), 0);
+        let mut sapper = Sapper::new(0, SapperBehavior::Bot, 4, 0.0);
+        assert_eq!(
+            sapper.guess(&field).map(|t| t.position),
+            Some(4),
+            "Fresh field"
+        );
+
+        sapper.set_mark(4, Some(Mark::Flag));
+        assert_ne!(sapper.guess(&field).map(|t| t.position), Some(4), "Flagged");
+
+        sapper.set_guessing(Guessing::Never);
+        assert!(sapper.guess(&field).is_none(), "Never");
+    }
+
+    #[test]
+    fn test_discover() {
+        let field = Field::new(3, 3, MinesAmount::Count(0), 0);
+        let mut sapper = Sapper::new(0, SapperBehavior::Player, 4, 0.0);
+
+        let is_discovering = |sapper: &mut Sapper| {
+            sapper.discover(&field);
+            return !sapper.get_events_mut().pull().is_empty();
+        };
+
+        sapper.set_mark(4, Some(Mark::Flag));
+        assert!(!is_discovering(&mut sapper), "Flagged");
+
+        sapper.set_mark(4, Some(Mark::Question));
+        assert!(!is_discovering(&mut sapper), "Questioned");
+
+        sapper.set_question_blocking(false);
+        assert!(is_discovering(&mut sapper), "Questioned and not blocking");
+
+        sapper.set_mark(4, None);
+        assert!(is_discovering(&mut sapper), "Unmarked");
+    }
+}
@@ -0,0 +1,99 @@
+// How the server rewards and punishes sappers. Marks are judged once the game is over, when the
+// sapper who has discovered the last safe cell gets the clear bonus too. A mine hit costs score
+// only when the sapper survives it, so with a single life the default rules only reward
+// discoveries, as the game always did
+#[derive(Clone, Copy)]
+pub struct Scoring {
+    pub discovery: u16,
+    pub cell: u16,
+    pub flag_bonus: u16,
+    pub flag_penalty: u16,
+    pub explosion_penalty: u16,
+    pub clear_bonus: u16,
+}
+
+impl Scoring {
+    pub const fn new() -> Self {
+        return Self {
+            discovery: 1,
+            cell: 0,
+            flag_bonus: 0,
+            flag_penalty: 0,
+            explosion_penalty: 10,
+            clear_bonus: 0,
+        };
+    }
+
+    // A discovery is rewarded itself and for every cell it has revealed, flood fill included
+    pub fn add_discovery(&self, score: u32, revealed: usize) -> u32 {
+        let revealed = u32::try_from(revealed).unwrap_or(u32::MAX);
+
+        return score
+            .saturating_add(u32::from(self.discovery))
+            .saturating_add(u32::from(self.cell).saturating_mul(revealed));
+    }
+
+    pub fn add_explosion(&self, score: u32) -> u32 {
+        return score.saturating_sub(u32::from(self.explosion_penalty));
+    }
+
+    pub fn add_flags(&self, score: u32, correct: usize, wrong: usize) -> u32 {
+        let correct = u32::try_from(correct).unwrap_or(u32::MAX);
+        let wrong = u32::try_from(wrong).unwrap_or(u32::MAX);
+
+        return score
+            .saturating_add(u32::from(self.flag_bonus).saturating_mul(correct))
+            .saturating_sub(u32::from(self.flag_penalty).saturating_mul(wrong));
+    }
+
+    pub fn add_clear(&self, score: u32) -> u32 {
+        return score.saturating_add(u32::from(self.clear_bonus));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scoring() {
+        let mut scoring = Scoring::new();
+        assert_eq!(scoring.add_discovery(5, 30), 6, "Discovery");
+        assert_eq!(scoring.add_flags(5, 2, 1), 5, "Flags ignored");
+
+        scoring.cell = 2;
+        scoring.flag_bonus = 3;
+        scoring.flag_penalty = 4;
+        scoring.clear_bonus = 7;
+        assert_eq!(scoring.add_discovery(5, 30), 66, "Cells");
+        assert_eq!(scoring.add_explosion(5), 0, "Explosion");
+        assert_eq!(scoring.add_flags(5, 2, 1), 7, "Flags");
+        assert_eq!(scoring.add_flags(5, 0, 2), 0, "Wrong flags");
+        assert_eq!(scoring.add_clear(5), 12, "Clear");
+    }
+
+    #[test]
+    fn test_scoring_saturation() {
+        let mut scoring = Scoring::new();
+        scoring.cell = u16::MAX;
+        scoring.flag_bonus = u16::MAX;
+        scoring.flag_penalty = u16::MAX;
+        scoring.clear_bonus = u16::MAX;
+
+        assert_eq!(
+            scoring.add_discovery(u32::from(u16::MAX), 1),
+            u32::from(u16::MAX) * 2 + 1,
+            "Beyond the old limit"
+        );
+        assert_eq!(
+            scoring.add_discovery(u32::MAX - 1, 0),
+            u32::MAX,
+            "Max discovery"
+        );
+        assert_eq!(scoring.add_discovery(0, usize::MAX), u32::MAX, "Max cells");
+        assert_eq!(scoring.add_flags(0, usize::MAX, 0), u32::MAX, "Max flags");
+        assert_eq!(scoring.add_flags(u32::MAX, 0, usize::MAX), 0, "Min flags");
+        assert_eq!(scoring.add_clear(u32::MAX), u32::MAX, "Max clear");
+        assert_eq!(scoring.add_explosion(0), 0, "Min explosion");
+    }
+}
@@ -0,0 +1,80 @@
+use crate::sapper::Guessing;
+use crate::solver::Reasoning;
+
+// How well a bot plays. Weaker bots see only the obvious, slip now and then and wander around on
+// the way to their targets
+#[derive(Clone, Copy, PartialEq)]
+pub enum Skill {
+    Novice,
+    Normal,
+    Expert,
+}
+
+impl Skill {
+    pub const ALL: [Self; 3] = [Self::Novice, Self::Normal, Self::Expert];
+    pub const DEFAULT: Self = Self::Expert; // Plays as flawlessly as bots always did
+
+    pub const fn get_name(self) -> &'static str {
+        return match self {
+            Self::Novice => "Novice",
+            Self::Normal => "Normal",
+            Self::Expert => "Expert",
+        };
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        return Self::ALL
+            .into_iter()
+            .find(|s| s.get_name().eq_ignore_ascii_case(name.trim()));
+    }
+
+    pub const fn get_reasoning(self) -> Reasoning {
+        return match self {
+            Self::Novice => Reasoning::Single,
+            Self::Normal => Reasoning::Pairs,
+            Self::Expert => Reasoning::Full,
+        };
+    }
+
+    // The chance to act on a neighbor of the target instead, which may hit a mine or put a
+    // wrong flag
+    pub const fn get_error_chance(self) -> f64 {
+        return match self {
+            Self::Novice => 0.08,
+            Self::Normal => 0.02,
+            Self::Expert => 0.0,
+        };
+    }
+
+    // The chance to make a step in a random direction instead of the one to the target
+    pub const fn get_wander_chance(self) -> f64 {
+        return match self {
+            Self::Novice => 0.3,
+            Self::Normal => 0.1,
+            Self::Expert => 0.0,
+        };
+    }
+
+    pub const fn get_guessing(self) -> Guessing {
+        return match self {
+            Self::Novice => Guessing::Cautious,
+            Self::Normal | Self::Expert => Guessing::Always,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_name() {
+        assert!(Skill::from_name("Novice") == Some(Skill::Novice), "Exact");
+        assert!(Skill::from_name(" expert ") == Some(Skill::Expert), "Loose");
+        assert!(Skill::from_name("Master").is_none(), "Unknown");
+
+        for skill in Skill::ALL {
+            assert!(Skill::from_name(skill.get_name()) == Some(skill), "Round trip");
+        }
+    }
+}
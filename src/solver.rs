@@ -0,0 +1,866 @@
+use crate::field::Field;
+use crate::utils;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+// Bigger groups of frontier cells are too slow to try every mines arrangement of
+const ENUMERATION_CELLS_MAX: usize = 20;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Knowledge {
+    Unknown,
+    Mined,
+    Safe(u8),
+}
+
+// How deep a sapper looks: at every number alone, at pairs of overlapping numbers too, or at
+// every mines arrangement of the frontier on top of that
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+pub enum Reasoning {
+    Single,
+    Pairs,
+    Full,
+}
+
+pub struct Solution {
+    pub safe: HashSet<u32>,
+    pub mined: HashSet<u32>,
+}
+
+// What's known about the mines which aren't found yet. The exact count is known only when the
+// field is generated with one, otherwise only the density is
+#[derive(Clone, Copy)]
+pub enum MinesLeft {
+    Count { mines: usize, cells: usize },
+    Density(f64),
+}
+
+// The chance of every frontier cell to be mined, and the one of every other unknown cell
+pub struct Probabilities {
+    pub frontier: HashMap<u32, f64>,
+    pub unconstrained: Option<f64>,
+}
+
+impl Solution {
+    pub fn is_empty(&self) -> bool {
+        return self.safe.is_empty() && self.mined.is_empty();
+    }
+}
+
+struct Constraint {
+    positions: Vec<u32>,
+    mines: usize,
+}
+
+impl Constraint {
+    fn is_subset_of(&self, other: &Self) -> bool {
+        return self.positions.len() < other.positions.len()
+            && self.positions.iter().all(|p| other.positions.contains(p));
+    }
+}
+
+// A constraint of a group under enumeration, referring to the cells by their indexes
+struct GroupConstraint {
+    mines: usize,
+    mines_placed: usize,
+    cells_left: usize,
+}
+
+impl GroupConstraint {
+    const fn is_satisfiable(&self) -> bool {
+        return self.mines_placed <= self.mines
+            && self.mines_placed + self.cells_left >= self.mines;
+    }
+}
+
+// Tries every mines arrangement of a group, counting how many of them have each cell mined.
+// Both are counted apart for every number of mines in the arrangement
+struct Enumeration {
+    constraints: Vec<GroupConstraint>,
+    cell_constraints: Vec<Vec<usize>>,
+    assignment: Vec<bool>,
+    mines: usize,
+    arrangements: Vec<usize>,
+    cells_mined: Vec<Vec<usize>>,
+}
+
+impl Enumeration {
+    fn run(&mut self, index: usize) {
+        if index >= self.assignment.len() {
+            if let Some(count) = self.arrangements.get_mut(self.mines) {
+                *count += 1;
+            }
+
+            if let Some(cells_mined) = self.cells_mined.get_mut(self.mines) {
+                for (count, is_mined) in cells_mined.iter_mut().zip(&self.assignment) {
+                    if *is_mined {
+                        *count += 1;
+                    }
+                }
+            }
+
+            return;
+        }
+
+        for is_mined in [false, true] {
+            if self.assign(index, is_mined, true) {
+                if let Some(item) = self.assignment.get_mut(index) {
+                    *item = is_mined;
+                }
+
+                self.mines += usize::from(is_mined);
+                self.run(index + 1);
+                self.mines -= usize::from(is_mined);
+            }
+
+            self.assign(index, is_mined, false);
+        }
+    }
+
+    // Places or takes back a cell, returns whether its constraints still can be satisfied
+    fn assign(&mut self, index: usize, is_mined: bool, is_placing: bool) -> bool {
+        let mut is_satisfiable = true;
+
+        for i in self.cell_constraints.get(index).into_iter().flatten() {
+            if let Some(constraint) = self.constraints.get_mut(*i) {
+                if is_placing {
+                    constraint.cells_left -= 1;
+                    constraint.mines_placed += usize::from(is_mined);
+                } else {
+                    constraint.cells_left += 1;
+                    constraint.mines_placed -= usize::from(is_mined);
+                }
+
+                is_satisfiable &= constraint.is_satisfiable();
+            }
+        }
+
+        return is_satisfiable;
+    }
+}
+
+// Deduces what it can from a fully known field, used by the generator
+pub fn solve(field: &Field, knowledge: &[Knowledge], mines_total: Option<usize>) -> Solution {
+    let numbers =
+        knowledge
+            .iter()
+            .enumerate()
+            .filter_map(|(i, item)| match (u32::try_from(i), *item) {
+                (Ok(position), Knowledge::Safe(mines_around)) => Some((position, mines_around)),
+                _ => None,
+            });
+
+    let constraints = collect_constraints(field, numbers, |position| {
+        return knowledge
+            .get(utils::u32_to_usize_saturating(position))
+            .copied()
+            .unwrap_or(Knowledge::Safe(0));
+    });
+
+    let mut solution = solve_constraints(&constraints, Reasoning::Full);
+
+    if let Some(mines_total) = mines_total {
+        let mut unknown = Vec::new();
+        let mut mined = 0;
+
+        for (i, item) in knowledge.iter().enumerate() {
+            match *item {
+                Knowledge::Unknown => {
+                    if let Ok(position) = u32::try_from(i) {
+                        unknown.push(position);
+                    }
+                }
+                Knowledge::Mined => {
+                    mined += 1;
+                }
+                Knowledge::Safe(_) => {}
+            }
+        }
+
+        if mines_total >= mined {
+            apply(&mut solution, &unknown, mines_total - mined);
+        }
+    }
+
+    return solution;
+}
+
+// Deduces what it can from a field as a sapper sees it, where only the discovered numbers, the
+// exploded mines and the cells which `is_mined` tells about are known
+pub fn solve_field<M: Fn(u32) -> bool>(
+    field: &Field,
+    is_mined: M,
+    reasoning: Reasoning,
+) -> Solution {
+    return solve_constraints(&collect_field_constraints(field, is_mined), reasoning);
+}
+
+// Sees the field the same way as `solve_field` does, but estimates chances instead
+pub fn get_probabilities<M: Fn(u32) -> bool>(
+    field: &Field,
+    is_mined: M,
+    mines_left: MinesLeft,
+) -> Probabilities {
+    return get_constraints_probabilities(&collect_field_constraints(field, is_mined), mines_left);
+}
+
+fn collect_field_constraints<M: Fn(u32) -> bool>(field: &Field, is_mined: M) -> Vec<Constraint> {
+    let numbers = field
+        .iter_cells()
+        .filter_map(|(position, cell)| cell.mines_around.map(|n| (position, n)));
+
+    return collect_constraints(field, numbers, |position| match field.get_cell(position) {
+        Some(cell) if cell.is_exploded => return Knowledge::Mined,
+        Some(cell) if cell.is_discovered() => return Knowledge::Safe(0),
+        Some(_) if is_mined(position) => return Knowledge::Mined,
+        Some(_) => return Knowledge::Unknown,
+        None => return Knowledge::Safe(0),
+    });
+}
+
+// Applies the simple rules to every number and to every pair of overlapping numbers first, and
+// enumerates the frontier only when they aren't enough
+fn solve_constraints(constraints: &[Constraint], reasoning: Reasoning) -> Solution {
+    let mut solution = Solution {
+        safe: HashSet::new(),
+        mined: HashSet::new(),
+    };
+
+    let index = index_constraints(constraints);
+
+    for constraint in constraints {
+        apply(&mut solution, &constraint.positions, constraint.mines);
+    }
+
+    if reasoning == Reasoning::Single {
+        return solution;
+    }
+
+    for subset in constraints {
+        let supersets = subset
+            .positions
+            .first()
+            .and_then(|p| index.get(p))
+            .into_iter()
+            .flatten()
+            .filter_map(|i| constraints.get(*i));
+
+        for superset in supersets {
+            if subset.is_subset_of(superset) && subset.mines <= superset.mines {
+                let rest = superset
+                    .positions
+                    .iter()
+                    .filter(|p| !subset.positions.contains(p))
+                    .copied()
+                    .collect::<Vec<_>>();
+
+                apply(&mut solution, &rest, superset.mines - subset.mines);
+            }
+        }
+    }
+
+    if solution.is_empty() && reasoning == Reasoning::Full {
+        for group in group_constraints(constraints, &index) {
+            let group = group
+                .iter()
+                .filter_map(|i| constraints.get(*i))
+                .collect::<Vec<_>>();
+
+            enumerate(&group, &mut solution);
+        }
+    }
+
+    return solution;
+}
+
+fn collect_constraints<N, K>(field: &Field, numbers: N, knowledge: K) -> Vec<Constraint>
+where
+    N: Iterator<Item = (u32, u8)>,
+    K: Fn(u32) -> Knowledge,
+{
+    let mut constraints = Vec::new();
+    let mut positions_near = Vec::with_capacity(8);
+
+    for (position, mines_around) in numbers {
+        let mut positions = Vec::with_capacity(8);
+        let mut mines_found = 0;
+
+        field.around_into(position, false, &mut positions_near);
+
+        for position_near in &positions_near {
+            match knowledge(*position_near) {
+                Knowledge::Unknown => {
+                    positions.push(*position_near);
+                }
+                Knowledge::Mined => {
+                    mines_found += 1;
+                }
+                Knowledge::Safe(_) => {}
+            }
+        }
+
+        let mines_around = usize::from(mines_around);
+
+        if !positions.is_empty() && mines_around >= mines_found {
+            positions.sort_unstable();
+
+            constraints.push(Constraint {
+                positions,
+                mines: mines_around - mines_found,
+            });
+        }
+    }
+
+    return constraints;
+}
+
+// Maps every cell to the constraints it's a part of
+fn index_constraints(constraints: &[Constraint]) -> HashMap<u32, Vec<usize>> {
+    let mut index = HashMap::<u32, Vec<usize>>::new();
+
+    for (i, constraint) in constraints.iter().enumerate() {
+        for position in &constraint.positions {
+            index.entry(*position).or_default().push(i);
+        }
+    }
+
+    return index;
+}
+
+// Splits the constraints into groups which share no cells, so each of them can be enumerated on
+// its own. The constraints of a group are listed in the order they connect to each other
+fn group_constraints(
+    constraints: &[Constraint],
+    index: &HashMap<u32, Vec<usize>>,
+) -> Vec<Vec<usize>> {
+    let mut groups = Vec::new();
+    let mut is_grouped = vec![false; constraints.len()];
+
+    for i in 0..constraints.len() {
+        if is_grouped.get(i).copied().unwrap_or(true) {
+            continue;
+        }
+
+        let mut group = Vec::new();
+        let mut pending = vec![i];
+
+        if let Some(item) = is_grouped.get_mut(i) {
+            *item = true;
+        }
+
+        while let Some(j) = pending.pop() {
+            for position in constraints.get(j).into_iter().flat_map(|c| &c.positions) {
+                for k in index.get(position).into_iter().flatten() {
+                    if let Some(item) = is_grouped.get_mut(*k) {
+                        if !*item {
+                            *item = true;
+                            pending.push(*k);
+                        }
+                    }
+                }
+            }
+
+            group.push(j);
+        }
+
+        groups.push(group);
+    }
+
+    return groups;
+}
+
+// A cell is certain once it's mined in all or none of the arrangements which satisfy the group
+fn enumerate(group: &[&Constraint], solution: &mut Solution) {
+    let cells = collect_cells(group);
+
+    let enumeration = match count_arrangements(group, &cells) {
+        Some(enumeration) => enumeration,
+        None => return,
+    };
+
+    let arrangements = enumeration.arrangements.iter().sum::<usize>();
+
+    // No arrangement at all means some of the known mines are wrong
+    if arrangements == 0 {
+        return;
+    }
+
+    for (i, position) in cells.iter().enumerate() {
+        let count = enumeration
+            .cells_mined
+            .iter()
+            .filter_map(|c| c.get(i))
+            .sum::<usize>();
+
+        if count == 0 {
+            solution.safe.insert(*position);
+        } else if count == arrangements {
+            solution.mined.insert(*position);
+        }
+    }
+}
+
+fn collect_cells(group: &[&Constraint]) -> Vec<u32> {
+    let mut cells = Vec::new();
+    let mut is_collected = HashSet::new();
+
+    for constraint in group {
+        for position in &constraint.positions {
+            if is_collected.insert(*position) {
+                cells.push(*position);
+            }
+        }
+    }
+
+    return cells;
+}
+
+fn count_arrangements(group: &[&Constraint], cells: &[u32]) -> Option<Enumeration> {
+    if cells.len() > ENUMERATION_CELLS_MAX {
+        return None;
+    }
+
+    let cell_indexes = cells
+        .iter()
+        .enumerate()
+        .map(|(i, position)| (*position, i))
+        .collect::<HashMap<_, _>>();
+
+    let mut cell_constraints = vec![Vec::new(); cells.len()];
+    let mut constraints = Vec::with_capacity(group.len());
+
+    for (i, constraint) in group.iter().enumerate() {
+        for position in &constraint.positions {
+            if let Some(item) = cell_indexes
+                .get(position)
+                .and_then(|j| cell_constraints.get_mut(*j))
+            {
+                item.push(i);
+            }
+        }
+
+        constraints.push(GroupConstraint {
+            mines: constraint.mines,
+            mines_placed: 0,
+            cells_left: constraint.positions.len(),
+        });
+    }
+
+    let mut enumeration = Enumeration {
+        constraints,
+        cell_constraints,
+        assignment: vec![false; cells.len()],
+        mines: 0,
+        arrangements: vec![0; cells.len() + 1],
+        cells_mined: vec![vec![0; cells.len()]; cells.len() + 1],
+    };
+
+    enumeration.run(0);
+
+    return Some(enumeration);
+}
+
+// Every group is enumerated apart, then the groups and the rest of the unknown cells are weighed
+// against each other by how many ways there are to hide the mines left among them
+fn get_constraints_probabilities(
+    constraints: &[Constraint],
+    mines_left: MinesLeft,
+) -> Probabilities {
+    let index = index_constraints(constraints);
+    let mut frontier = HashMap::new();
+    let mut groups = Vec::new();
+
+    for group in group_constraints(constraints, &index) {
+        let group = group
+            .iter()
+            .filter_map(|i| constraints.get(*i))
+            .collect::<Vec<_>>();
+
+        let cells = collect_cells(&group);
+
+        match count_arrangements(&group, &cells) {
+            Some(enumeration) if enumeration.arrangements.iter().any(|n| *n > 0) => {
+                groups.push((cells, enumeration));
+            }
+            // Too big or contradicting groups only get the rough chance of their numbers
+            _ => {
+                for constraint in group {
+                    let chance = utils::usize_to_f64(constraint.mines)
+                        / utils::usize_to_f64(constraint.positions.len());
+
+                    for position in &constraint.positions {
+                        let item = frontier.entry(*position).or_insert(chance);
+                        *item = f64::max(*item, chance);
+                    }
+                }
+            }
+        }
+    }
+
+    let unconstrained = match mines_left {
+        MinesLeft::Count { mines, cells } => {
+            let enumerated = groups.iter().map(|(c, _)| c.len()).sum::<usize>();
+            get_count_probabilities(
+                &groups,
+                mines,
+                cells.saturating_sub(enumerated),
+                &mut frontier,
+            )
+        }
+        MinesLeft::Density(density) => {
+            get_density_probabilities(&groups, density, &mut frontier);
+            Some(density.clamp(0.0, 1.0))
+        }
+    };
+
+    return Probabilities {
+        frontier,
+        unconstrained,
+    };
+}
+
+// With only the density known, every additional mine in an arrangement makes it less or more
+// likely by the same ratio
+fn get_density_probabilities(
+    groups: &[(Vec<u32>, Enumeration)],
+    density: f64,
+    frontier: &mut HashMap<u32, f64>,
+) {
+    let density = density.clamp(0.0, 1.0);
+    let mut weight = 1.0;
+    let mut weights = Vec::new();
+
+    for _ in 0..=ENUMERATION_CELLS_MAX {
+        weights.push(weight);
+
+        if density < 1.0 {
+            weight *= density / (1.0 - density);
+        }
+    }
+
+    if density >= 1.0 {
+        weights = vec![0.0; weights.len()];
+
+        if let Some(weight) = weights.last_mut() {
+            *weight = 1.0;
+        }
+    }
+
+    for (cells, enumeration) in groups {
+        let distribution = to_distribution(enumeration);
+        let mut total = 0.0;
+
+        for (k, chance) in distribution.iter().enumerate() {
+            total += chance * weights.get(k).copied().unwrap_or(0.0);
+        }
+
+        for (i, position) in cells.iter().enumerate() {
+            let mut chance = 0.0;
+
+            for (k, cells_mined) in enumeration.cells_mined.iter().enumerate() {
+                chance += to_ratio(cells_mined.get(i), enumeration)
+                    * weights.get(k).copied().unwrap_or(0.0);
+            }
+
+            frontier.insert(
+                *position,
+                if total > 0.0 {
+                    chance / total
+                } else {
+                    0.0
+                },
+            );
+        }
+    }
+}
+
+// With the exact count known, the frontier can't hold more mines than there are left, and every
+// way to place the rest among the unconstrained cells counts
+fn get_count_probabilities(
+    groups: &[(Vec<u32>, Enumeration)],
+    mines: usize,
+    cells: usize,
+    frontier: &mut HashMap<u32, f64>,
+) -> Option<f64> {
+    let distributions = groups
+        .iter()
+        .map(|(_, e)| to_distribution(e))
+        .collect::<Vec<_>>();
+
+    // The distribution of mines among all the groups before and after every one of them
+    let mut prefixes = vec![vec![1.0]];
+    let mut suffixes = vec![vec![1.0]];
+
+    for distribution in &distributions {
+        let prefix = prefixes
+            .last()
+            .map_or_else(Vec::new, |p| convolve(p, distribution));
+        prefixes.push(prefix);
+    }
+
+    for distribution in distributions.iter().rev() {
+        let suffix = suffixes
+            .last()
+            .map_or_else(Vec::new, |s| convolve(s, distribution));
+        suffixes.push(suffix);
+    }
+
+    suffixes.reverse();
+
+    let total = prefixes.last().cloned().unwrap_or_default();
+    let weights = get_count_weights(mines, cells, total.len());
+    let weight_of = |m: usize| return weights.get(m).copied().unwrap_or(0.0);
+
+    let mut sum = 0.0;
+    let mut unconstrained_sum = 0.0;
+
+    for (m, chance) in total.iter().enumerate() {
+        sum += chance * weight_of(m);
+        unconstrained_sum += chance * weight_of(m) * utils::usize_to_f64(mines.saturating_sub(m));
+    }
+
+    for (g, (positions, enumeration)) in groups.iter().enumerate() {
+        let others = match (prefixes.get(g), suffixes.get(g + 1)) {
+            (Some(prefix), Some(suffix)) => convolve(prefix, suffix),
+            _ => continue,
+        };
+
+        for (i, position) in positions.iter().enumerate() {
+            let mut chance = 0.0;
+
+            for (k, cells_mined) in enumeration.cells_mined.iter().enumerate() {
+                let ratio = to_ratio(cells_mined.get(i), enumeration);
+
+                for (m, other) in others.iter().enumerate() {
+                    chance += ratio * other * weight_of(k + m);
+                }
+            }
+
+            // Nothing fits the count, so the group is left on its own
+            if sum <= 0.0 {
+                chance = enumeration
+                    .cells_mined
+                    .iter()
+                    .map(|c| to_ratio(c.get(i), enumeration))
+                    .sum();
+            } else {
+                chance /= sum;
+            }
+
+            frontier.insert(*position, chance);
+        }
+    }
+
+    if cells == 0 {
+        return None;
+    } else if sum <= 0.0 {
+        return Some(f64::min(
+            utils::usize_to_f64(mines) / utils::usize_to_f64(cells),
+            1.0,
+        ));
+    } else {
+        return Some(unconstrained_sum / sum / utils::usize_to_f64(cells));
+    }
+}
+
+// How many ways there are to hide the rest of the mines among the unconstrained cells, for every
+// number of mines on the frontier, relative to the most likely number
+fn get_count_weights(mines: usize, cells: usize, count: usize) -> Vec<f64> {
+    let mut logs = vec![f64::NEG_INFINITY; count];
+    let mut log = 0.0;
+    let mut is_anchored = false;
+
+    for (m, item) in logs.iter_mut().enumerate() {
+        let rest = match mines.checked_sub(m) {
+            Some(rest) if rest <= cells => rest,
+            _ => continue,
+        };
+
+        // C(n, r) = C(n, r + 1) * (r + 1) / (n - r)
+        if is_anchored {
+            log += (utils::usize_to_f64(rest + 1) / utils::usize_to_f64(cells - rest)).ln();
+        }
+
+        is_anchored = true;
+        *item = log;
+    }
+
+    let max = logs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    if max.is_infinite() {
+        return vec![0.0; count];
+    }
+
+    return logs.iter().map(|l| return (l - max).exp()).collect();
+}
+
+// The chance of a group to hold every number of mines, if each of its arrangements is as likely
+fn to_distribution(enumeration: &Enumeration) -> Vec<f64> {
+    return enumeration
+        .arrangements
+        .iter()
+        .map(|n| return to_ratio(Some(n), enumeration))
+        .collect();
+}
+
+fn to_ratio(count: Option<&usize>, enumeration: &Enumeration) -> f64 {
+    let total = enumeration.arrangements.iter().sum::<usize>();
+
+    if total == 0 {
+        return 0.0;
+    }
+
+    return utils::usize_to_f64(count.copied().unwrap_or(0)) / utils::usize_to_f64(total);
+}
+
+fn convolve(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut result = vec![0.0; (a.len() + b.len()).saturating_sub(1)];
+
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            if let Some(item) = result.get_mut(i + j) {
+                *item += x * y;
+            }
+        }
+    }
+
+    return result;
+}
+
+fn apply(solution: &mut Solution, positions: &[u32], mines: usize) {
+    if positions.is_empty() {
+        return;
+    }
+
+    if mines == 0 {
+        solution.safe.extend(positions);
+    } else if mines == positions.len() {
+        solution.mined.extend(positions);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::EventManager;
+    use crate::field::MinesAmount;
+
+    fn discover(field: &mut Field, positions: &[u32]) {
+        for position in positions {
+            field.discover(*position, &mut EventManager::new());
+        }
+    }
+
+    #[test]
+    fn test_solve_field() {
+        // A 1-2-1 pattern along a bounded edge, where the middle numbers overlap the edge ones
+        //  * . * .
+        //  1 2 2 1
+        let mut field = Field::new(4, 2, MinesAmount::Count(0), 0);
+        field.set_mines(HashSet::from([0, 2]));
+        discover(&mut field, &[4, 5, 6, 7]);
+
+        let solution = solve_field(&field, |_| false, Reasoning::Full);
+        assert_eq!(solution.mined, HashSet::from([2]), "Mined");
+        assert_eq!(solution.safe, HashSet::from([1]), "Safe");
+
+        let solution = solve_field(&field, |p| p == 2, Reasoning::Full);
+        assert!(solution.safe.contains(&3), "Known mine");
+    }
+
+    #[test]
+    fn test_enumeration() {
+        // Neither of the numbers is a subset of the other, but only the arrangements with the
+        // first cell safe and the last one mined satisfy both
+        let constraints = [
+            Constraint {
+                positions: vec![0, 1, 2],
+                mines: 1,
+            },
+            Constraint {
+                positions: vec![1, 2, 3],
+                mines: 2,
+            },
+        ];
+
+        let solution = solve_constraints(&constraints, Reasoning::Pairs);
+        assert!(solution.is_empty(), "Pairs only");
+
+        let solution = solve_constraints(&constraints, Reasoning::Full);
+        assert_eq!(solution.safe, HashSet::from([0]), "Safe");
+        assert_eq!(solution.mined, HashSet::from([3]), "Mined");
+
+        let constraints = [
+            Constraint {
+                positions: vec![0, 1],
+                mines: 2,
+            },
+            Constraint {
+                positions: vec![1, 2],
+                mines: 0,
+            },
+        ];
+
+        let solution = solve_constraints(&constraints[..1], Reasoning::Single);
+        assert_eq!(solution.mined, HashSet::from([0, 1]), "Simple rule");
+
+        let mut solution = Solution {
+            safe: HashSet::new(),
+            mined: HashSet::new(),
+        };
+
+        enumerate(&constraints.iter().collect::<Vec<_>>(), &mut solution);
+        assert!(solution.is_empty(), "Contradiction");
+    }
+
+    #[test]
+    fn test_probabilities() {
+        // Either the middle cell is mined alone, or both of the outer ones are
+        let constraints = [
+            Constraint {
+                positions: vec![0, 1],
+                mines: 1,
+            },
+            Constraint {
+                positions: vec![1, 2],
+                mines: 1,
+            },
+        ];
+
+        let chance = |probabilities: &Probabilities, position: u32| {
+            return probabilities
+                .frontier
+                .get(&position)
+                .copied()
+                .unwrap_or(-1.0);
+        };
+
+        let mines_left = MinesLeft::Count { mines: 2, cells: 3 };
+        let probabilities = get_constraints_probabilities(&constraints, mines_left);
+        assert!(
+            (chance(&probabilities, 0) - 1.0).abs() < 0.001,
+            "Outer by count"
+        );
+        assert!(chance(&probabilities, 1).abs() < 0.001, "Middle by count");
+        assert!(probabilities.unconstrained.is_none(), "No unconstrained");
+
+        let mines_left = MinesLeft::Count { mines: 2, cells: 4 };
+        let probabilities = get_constraints_probabilities(&constraints, mines_left);
+        assert!((chance(&probabilities, 1) - 0.5).abs() < 0.001, "Middle");
+        assert!((chance(&probabilities, 2) - 0.5).abs() < 0.001, "Outer");
+        assert!(
+            (probabilities.unconstrained.unwrap() - 0.5).abs() < 0.001,
+            "Unconstrained"
+        );
+
+        let probabilities = get_constraints_probabilities(&constraints, MinesLeft::Density(0.2));
+        assert!(
+            (chance(&probabilities, 1) - 0.8).abs() < 0.001,
+            "Middle by density"
+        );
+        assert!(
+            (probabilities.unconstrained.unwrap() - 0.2).abs() < 0.001,
+            "Unconstrained by density"
+        );
+    }
+}
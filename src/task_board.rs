@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+// The targets claimed by the bots who share the work, so none of them heads to a cell another one
+// has already taken
+pub struct TaskBoard {
+    claims: HashMap<u32, u8>,
+}
+
+impl TaskBoard {
+    pub fn new() -> Self {
+        return Self {
+            claims: HashMap::new(),
+        };
+    }
+
+    // Returns whether the target is the sapper's now, it isn't if another one has claimed it first
+    pub fn claim(&mut self, position: u32, id: u8) -> bool {
+        return *self.claims.entry(position).or_insert(id) == id;
+    }
+
+    pub fn release(&mut self, id: u8) {
+        self.claims.retain(|_, claimer| *claimer != id);
+    }
+
+    pub fn retain<F: Fn(u32, u8) -> bool>(&mut self, is_valid: F) {
+        self.claims
+            .retain(|position, claimer| is_valid(*position, *claimer));
+    }
+
+    pub fn is_claimed_by_other(&self, position: u32, id: u8) -> bool {
+        return self.claims.get(&position).map_or(false, |c| *c != id);
+    }
+
+    pub fn has_claims_of_other(&self, id: u8) -> bool {
+        return self.claims.values().any(|c| *c != id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_claims() {
+        let mut board = TaskBoard::new();
+        assert!(board.claim(5, 0), "Free");
+        assert!(board.claim(5, 0), "Own");
+        assert!(!board.claim(5, 1), "Taken");
+        assert!(board.is_claimed_by_other(5, 1), "Other");
+        assert!(!board.is_claimed_by_other(5, 0), "Not other");
+        assert!(!board.has_claims_of_other(0), "Only own");
+
+        board.release(0);
+        assert!(!board.has_claims_of_other(1), "Released");
+        assert!(board.claim(5, 1), "Free again");
+
+        board.claim(6, 0);
+        board.retain(|position, _| position != 6);
+        assert!(board.claim(6, 1), "Retained");
+    }
+}
@@ -0,0 +1,168 @@
+const SQUARE_ID: u8 = 0;
+const HEX_ID: u8 = 1;
+
+const SQUARE_NEIGHBORS: [(i32, i32); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+// Odd rows are shifted right by a half of a cell
+const HEX_NEIGHBORS_EVEN: [(i32, i32); 6] = [(-1, -1), (0, -1), (-1, 0), (1, 0), (-1, 1), (0, 1)];
+const HEX_NEIGHBORS_ODD: [(i32, i32); 6] = [(0, -1), (1, -1), (-1, 0), (1, 0), (0, 1), (1, 1)];
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    pub const ALL: [Self; 8] = [
+        Self::Left,
+        Self::Right,
+        Self::Up,
+        Self::Down,
+        Self::UpLeft,
+        Self::UpRight,
+        Self::DownLeft,
+        Self::DownRight,
+    ];
+}
+
+pub trait Topology {
+    fn get_id(&self) -> u8;
+
+    fn get_neighbors(&self, y: i32) -> &'static [(i32, i32)];
+
+    fn get_step(&self, direction: Direction, y: i32) -> Option<(i32, i32)>;
+
+    fn to_distance(&self, y: i32, shift_x: i32, shift_y: i32) -> u32;
+
+    fn get_row_offset(&self, y: i32) -> usize;
+}
+
+pub fn from_id(id: u8) -> Box<dyn Topology> {
+    return match id {
+        HEX_ID => Box::new(HexTopology {}),
+        _ => Box::new(SquareTopology {}),
+    };
+}
+
+pub struct SquareTopology {}
+
+impl Topology for SquareTopology {
+    fn get_id(&self) -> u8 {
+        return SQUARE_ID;
+    }
+
+    fn get_neighbors(&self, _y: i32) -> &'static [(i32, i32)] {
+        return &SQUARE_NEIGHBORS;
+    }
+
+    fn get_step(&self, direction: Direction, _y: i32) -> Option<(i32, i32)> {
+        return match direction {
+            Direction::Left => Some((-1, 0)),
+            Direction::Right => Some((1, 0)),
+            Direction::Up => Some((0, -1)),
+            Direction::Down => Some((0, 1)),
+            _ => None,
+        };
+    }
+
+    fn to_distance(&self, _y: i32, shift_x: i32, shift_y: i32) -> u32 {
+        return shift_x.unsigned_abs() + shift_y.unsigned_abs();
+    }
+
+    fn get_row_offset(&self, _y: i32) -> usize {
+        return 0;
+    }
+}
+
+pub struct HexTopology {}
+
+impl HexTopology {
+    const fn is_odd(y: i32) -> bool {
+        return y.rem_euclid(2) == 1;
+    }
+
+    // Converts odd row offset coordinates to the axial ones
+    const fn to_axial(x: i32, y: i32) -> (i32, i32) {
+        return (x - (y - y.rem_euclid(2)).div_euclid(2), y);
+    }
+}
+
+impl Topology for HexTopology {
+    fn get_id(&self) -> u8 {
+        return HEX_ID;
+    }
+
+    fn get_neighbors(&self, y: i32) -> &'static [(i32, i32)] {
+        if Self::is_odd(y) {
+            return &HEX_NEIGHBORS_ODD;
+        } else {
+            return &HEX_NEIGHBORS_EVEN;
+        }
+    }
+
+    fn get_step(&self, direction: Direction, y: i32) -> Option<(i32, i32)> {
+        let shift = i32::from(Self::is_odd(y));
+
+        // Straight up and down steps zigzag, so they keep a sapper around the same column
+        return Some(match direction {
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::UpLeft => (shift - 1, -1),
+            Direction::UpRight => (shift, -1),
+            Direction::DownLeft => (shift - 1, 1),
+            Direction::DownRight => (shift, 1),
+        });
+    }
+
+    fn to_distance(&self, y: i32, shift_x: i32, shift_y: i32) -> u32 {
+        let (q1, r1) = Self::to_axial(0, y);
+        let (q2, r2) = Self::to_axial(shift_x, y + shift_y);
+        let q = q2 - q1;
+        let r = r2 - r1;
+        return (q.unsigned_abs() + r.unsigned_abs() + (q + r).unsigned_abs()).div_euclid(2);
+    }
+
+    fn get_row_offset(&self, y: i32) -> usize {
+        return usize::from(Self::is_odd(y));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_distance() {
+        let hex = HexTopology {};
+
+        for y in 0..2 {
+            for (x, y_shift) in hex.get_neighbors(y) {
+                assert_eq!(hex.to_distance(y, *x, *y_shift), 1, "Neighbors");
+            }
+        }
+
+        assert_eq!(hex.to_distance(0, 0, 0), 0, "Same cell");
+        assert_eq!(hex.to_distance(0, 3, 0), 3, "Same row");
+        assert_eq!(hex.to_distance(0, 1, 2), 2, "Two rows down");
+        assert_eq!(hex.to_distance(1, 0, 2), 2, "Two rows down from an odd row");
+        assert_eq!(hex.to_distance(0, 0, 2), 2, "Zigzag");
+    }
+}
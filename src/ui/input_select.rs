@@ -0,0 +1,113 @@
+use crate::ui::Element;
+use crate::ui::Event;
+use std::any::Any;
+use termwiz::input::InputEvent;
+use termwiz::input::KeyCode;
+use termwiz::input::KeyEvent;
+
+const TOOLTIP: &str = "Use left and right arrow keys to choose an option.";
+
+pub struct InputSelect {
+    pub label: &'static str,
+    pub options: Vec<String>,
+    pub selected: usize,
+    pub tooltip_extra: Option<&'static str>,
+    events: Vec<Event>,
+}
+
+impl InputSelect {
+    pub fn new(label: &'static str, options: &[&str], tooltip_extra: Option<&'static str>) -> Self {
+        return Self {
+            label,
+            options: options.iter().map(|o| (*o).to_owned()).collect(),
+            selected: 0,
+            tooltip_extra,
+            events: Vec::new(),
+        };
+    }
+
+    pub fn get_value(&self) -> Option<&str> {
+        return self.options.get(self.selected).map(String::as_str);
+    }
+
+    pub fn select(&mut self, option: &str) {
+        for (i, o) in self.options.iter().enumerate() {
+            if o == option {
+                self.selected = i;
+                break;
+            }
+        }
+    }
+}
+
+impl Element for InputSelect {
+    fn update(&mut self, input: &InputEvent) {
+        match input {
+            InputEvent::Key(KeyEvent {
+                key: KeyCode::LeftArrow,
+                ..
+            }) if self.selected > 0 => {
+                self.selected -= 1;
+                self.events.push(Event::PageChanged);
+            }
+            InputEvent::Key(KeyEvent {
+                key: KeyCode::RightArrow,
+                ..
+            }) if self.selected + 1 < self.options.len() => {
+                self.selected += 1;
+                self.events.push(Event::PageChanged);
+            }
+            _ => {}
+        }
+    }
+
+    fn render(&self) -> String {
+        return format!(" $ {} : {} ", self.label, self.get_value().unwrap_or(""));
+    }
+
+    fn pull_events_into(&mut self, buffer: &mut Vec<Event>) {
+        buffer.append(&mut self.events);
+    }
+
+    fn get_label(&self) -> &str {
+        return self.label;
+    }
+
+    fn is_active(&self) -> bool {
+        return true;
+    }
+
+    fn is_selectable(&self) -> bool {
+        return true;
+    }
+
+    fn get_tooltip(&self) -> Option<&'static str> {
+        return Some(TOOLTIP);
+    }
+
+    fn get_tooltip_extra(&self) -> Option<&'static str> {
+        return self.tooltip_extra;
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        return self;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select() {
+        let mut input = InputSelect::new("Mode", &["Density", "Count"], None);
+
+        // An equal option from anywhere else than the literal the input was built with
+        let option = ["Co", "unt"].concat();
+        input.select(&option);
+        assert_eq!(input.get_value(), Some("Count"), "Equal value");
+
+        input.select("Missing");
+        assert_eq!(input.get_value(), Some("Count"), "Missing value");
+    }
+}
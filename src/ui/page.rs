@@ -1,5 +1,6 @@
 use crate::ui::button::Button;
 use crate::ui::input_number::InputNumber;
+use crate::ui::input_select::InputSelect;
 use crate::ui::input_text::InputText;
 use crate::ui::Element;
 use crate::ui::Event;
@@ -162,6 +163,13 @@ impl Page {
             .and_then(|e| e.as_any().downcast_mut::<InputNumber>());
     }
 
+    // TODO: Avoid WET code
+    pub fn fetch_input_select_mut(&mut self, label: &str) -> Option<&mut InputSelect> {
+        return self
+            .fetch_element_mut(label)
+            .and_then(|e| e.as_any().downcast_mut::<InputSelect>());
+    }
+
     // TODO: Avoid WET code
     pub fn fetch_input_text_mut(&mut self, label: &str) -> Option<&mut InputText> {
         return self
@@ -2,18 +2,29 @@ use rand::prelude::*;
 use std::time::Duration;
 use std::time::SystemTime;
 
-pub fn is_chance(chance: f64) -> bool {
-    return rand::thread_rng().gen::<f64>() < chance;
+pub fn is_chance<R: Rng>(rng: &mut R, chance: f64) -> bool {
+    return rng.gen::<f64>() < chance;
 }
 
-pub const fn difference(n1: u16, n2: u16) -> u16 {
-    if n1 > n2 {
-        return n1 - n2;
+// Picks the shortest way around a loop of the given length
+pub const fn wrap_shift(shift: i32, length: i32) -> i32 {
+    if length <= 0 {
+        return shift;
+    }
+
+    let shift = shift.rem_euclid(length);
+
+    if shift * 2 > length {
+        return shift - length;
     } else {
-        return n2 - n1;
+        return shift;
     }
 }
 
+pub fn u32_to_usize_saturating(value: u32) -> usize {
+    return usize::try_from(value).unwrap_or(usize::MAX);
+}
+
 #[allow(
     clippy::as_conversions,
     clippy::cast_possible_truncation,
@@ -23,6 +34,55 @@ pub const fn f64_to_u8_saturating_floor(value: f64) -> u8 {
     return value as u8;
 }
 
+#[allow(
+    clippy::as_conversions,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+pub const fn f64_to_u16_saturating_floor(value: f64) -> u16 {
+    return value as u16;
+}
+
+#[allow(
+    clippy::as_conversions,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+pub const fn f64_to_u32_saturating_floor(value: f64) -> u32 {
+    return value as u32;
+}
+
+#[allow(clippy::as_conversions, clippy::cast_precision_loss)]
+pub const fn usize_to_f64(value: usize) -> f64 {
+    return value as f64;
+}
+
+// Fits a number into four characters, shortening big ones to thousands, millions or billions
+pub fn format_short(value: u32) -> String {
+    if value < 10_000 {
+        return format!("{:04}", value);
+    } else if value < 1_000_000 {
+        return format!("{:03}K", value / 1_000);
+    } else if value < 1_000_000_000 {
+        return format!("{:03}M", value / 1_000_000);
+    } else {
+        return format!("{:03}B", value / 1_000_000_000);
+    }
+}
+
+// Fits a time into five characters: minutes and seconds for the first hundred minutes, hours and
+// minutes after that, up to `99h59`
+pub fn format_time(seconds: u32) -> String {
+    let minutes = seconds / 60;
+
+    if minutes < 100 {
+        return format!("{:02}:{:02}", minutes, seconds % 60);
+    } else {
+        let minutes = std::cmp::min(minutes, 99 * 60 + 59);
+        return format!("{:02}h{:02}", minutes / 60, minutes % 60);
+    }
+}
+
 pub struct Timer {
     duration: Duration,
     target: SystemTime,
@@ -59,6 +119,40 @@ impl Timer {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_wrap_shift() {
+        assert_eq!(wrap_shift(2, 10), 2, "Forward");
+        assert_eq!(wrap_shift(-2, 10), -2, "Backward");
+        assert_eq!(wrap_shift(8, 10), -2, "Forward through the edge");
+        assert_eq!(wrap_shift(-8, 10), 2, "Backward through the edge");
+        assert_eq!(wrap_shift(5, 10), 5, "Half");
+        assert_eq!(wrap_shift(0, 10), 0, "Zero");
+        assert_eq!(wrap_shift(3, 0), 3, "Empty loop");
+    }
+
+    #[test]
+    fn test_format_short() {
+        assert_eq!(format_short(0), "0000", "Zero");
+        assert_eq!(format_short(9_999), "9999", "Max digits");
+        assert_eq!(format_short(10_000), "010K", "Min thousands");
+        assert_eq!(format_short(999_999), "999K", "Max thousands");
+        assert_eq!(format_short(1_000_000), "001M", "Min millions");
+        assert_eq!(format_short(999_999_999), "999M", "Max millions");
+        assert_eq!(format_short(1_000_000_000), "001B", "Min billions");
+        assert_eq!(format_short(u32::MAX), "004B", "Max");
+    }
+
+    #[test]
+    fn test_format_time() {
+        assert_eq!(format_time(0), "00:00", "Zero");
+        assert_eq!(format_time(59), "00:59", "Seconds");
+        assert_eq!(format_time(61), "01:01", "Minutes");
+        assert_eq!(format_time(5999), "99:59", "Last minutes");
+        assert_eq!(format_time(6000), "01h40", "Hours");
+        assert_eq!(format_time(600 * 60), "10h00", "Max limit");
+        assert_eq!(format_time(u32::MAX), "99h59", "Clamped");
+    }
+
     #[test]
     fn test_f64_to_u8_saturating_floor() {
         assert_eq!(f64_to_u8_saturating_floor(100.0), 100, "Basic");
@@ -84,4 +178,22 @@ mod tests {
 
         assert_eq!(f64_to_u8_saturating_floor(f64::NAN), 0, "Not a number");
     }
+
+    #[test]
+    fn test_f64_to_u16_saturating_floor() {
+        assert_eq!(f64_to_u16_saturating_floor(1000.0), 1000, "Basic");
+        assert_eq!(f64_to_u16_saturating_floor(1000.9), 1000, "Rounding");
+
+        assert_eq!(f64_to_u16_saturating_floor(0.0), 0, "Min");
+        assert_eq!(f64_to_u16_saturating_floor(65535.0), u16::MAX, "Max");
+
+        assert_eq!(f64_to_u16_saturating_floor(-1.0), 0, "Less than min");
+        assert_eq!(
+            f64_to_u16_saturating_floor(65536.0),
+            u16::MAX,
+            "Greater than max"
+        );
+
+        assert_eq!(f64_to_u16_saturating_floor(f64::NAN), 0, "Not a number");
+    }
 }